@@ -0,0 +1,140 @@
+//! Benchmarks for loading plugin descriptions and querying the loaded model, comparing the
+//! sequential iterator API with the (parallel) rayon API.
+//!
+//! Run with `cargo bench`, and with `cargo bench --no-default-features` to measure the loader
+//! without rayon. The parallel query benchmarks are only run when the `rayon` feature is enabled.
+
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use lv2_se_bundle::rdf_util::{Graph, Iri, LangTag, Literal, Node};
+use lv2_se_bundle::bundle_model::{HasRelatedSet, Localized, NameRelation, RequiresRelation};
+use lv2_se_bundle::bundle_model::constants::{HostFeature, IriConstant, PluginType, PortType};
+use lv2_se_bundle::bundle_model::loader::load_plugins;
+use lv2_se_bundle::bundle_model::catalog::PluginCatalog;
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const DOAP_NAME: &str = "http://usefulinc.com/ns/doap#name";
+const LV2_PLUGIN: &str = "http://lv2plug.in/ns/lv2core#Plugin";
+const LV2_BINARY: &str = "http://lv2plug.in/ns/lv2core#binary";
+const LV2_REQUIRED_FEATURE: &str = "http://lv2plug.in/ns/lv2core#requiredFeature";
+const LV2_PORT: &str = "http://lv2plug.in/ns/lv2core#port";
+const LV2_INDEX: &str = "http://lv2plug.in/ns/lv2core#index";
+const LV2_SYMBOL: &str = "http://lv2plug.in/ns/lv2core#symbol";
+const LV2_NAME: &str = "http://lv2plug.in/ns/lv2core#name";
+
+const PLUGIN_COUNT: usize = 500;
+const PORTS_PER_PLUGIN: usize = 8;
+const QUERY_ROUNDS: usize = 20;
+
+fn iri(text: &str) -> Iri {
+    Iri::new(text.to_string()).unwrap()
+}
+
+fn iri_node(text: &str) -> Node {
+    Node::Iri(iri(text))
+}
+
+fn lang_literal(text: &str, lang: &str) -> Node {
+    Node::Literal(Literal::with_lang_tag(text.to_string(), LangTag::new(lang.to_string()).unwrap()))
+}
+
+/// Builds a bundle graph describing many plugins, with names in several languages, a few plugin
+/// types and required features, and several ports each.
+fn bundle_graph() -> Graph {
+    let plugin_types = [PluginType::Compressor, PluginType::Lowpass, PluginType::Reverb, PluginType::Instrument];
+    let features = [HostFeature::UridMap, HostFeature::Options, HostFeature::BoundedBlockLength];
+    let mut graph = Graph::new();
+    for plugin_index in 0..PLUGIN_COUNT {
+        let plugin = iri_node(&format!("urn:bench:plugin{}", plugin_index));
+        graph.insert(plugin.clone(), iri(RDF_TYPE), iri_node(LV2_PLUGIN));
+        graph.insert(plugin.clone(), iri(RDF_TYPE), iri_node(plugin_types[plugin_index % plugin_types.len()].iri()));
+        graph.insert(plugin.clone(), iri(LV2_BINARY), iri_node("plugin.so"));
+        for (lang, name) in &[("en", "Plugin"), ("de", "Erweiterung"), ("fr", "Greffon")] {
+            graph.insert(plugin.clone(), iri(DOAP_NAME), lang_literal(&format!("{} {}", name, plugin_index), lang));
+        }
+        for feature in features.iter().take(plugin_index % features.len() + 1) {
+            graph.insert(plugin.clone(), iri(LV2_REQUIRED_FEATURE), iri_node(feature.iri()));
+        }
+        for port_index in 0..PORTS_PER_PLUGIN {
+            let port = Node::BlankNode(format!("plugin{}port{}", plugin_index, port_index));
+            graph.insert(plugin.clone(), iri(LV2_PORT), port.clone());
+            graph.insert(port.clone(), iri(RDF_TYPE), iri_node(PortType::InputPort.iri()));
+            graph.insert(port.clone(), iri(RDF_TYPE), iri_node(PortType::Control.iri()));
+            graph.insert(port.clone(), iri(LV2_INDEX), Node::Literal(Literal::new(port_index.to_string())));
+            graph.insert(port.clone(), iri(LV2_SYMBOL), Node::Literal(Literal::new(format!("port{}", port_index))));
+            graph.insert(port, iri(LV2_NAME), lang_literal(&format!("Port {}", port_index), "en"));
+        }
+    }
+    graph
+}
+
+/// Runs a benchmark function repeatedly and reports the mean time per run.
+fn bench<F: FnMut() -> usize>(name: &str, runs: usize, mut f: F) {
+    let mut total = Duration::new(0, 0);
+    let mut checksum = 0;
+    for _ in 0..runs {
+        let start = Instant::now();
+        checksum += black_box(f());
+        total += start.elapsed();
+    }
+    println!("{:<40} {:>12.3?} per run (checksum {})", name, total / runs as u32, checksum);
+}
+
+fn main() {
+    let graph = bundle_graph();
+    bench("load_plugins", 5, || load_plugins(&graph).unwrap().len());
+
+    let mut catalog = PluginCatalog::new();
+    catalog.add_bundle(&graph, Path::new("/bench.lv2")).unwrap();
+    let langs = [LangTag::new(String::from("fr-CA")).unwrap()];
+
+    bench("names (sequential)", QUERY_ROUNDS, || {
+        catalog.plugins_seq_iter()
+            .map(|plugin| HasRelatedSet::<NameRelation, Literal>::seq_set_iter(plugin.info()).count())
+            .sum()
+    });
+    bench("best name (sequential)", QUERY_ROUNDS, || {
+        catalog.plugins_seq_iter()
+            .filter_map(|plugin| plugin.info().best_name(&langs))
+            .count()
+    });
+    bench("required feature check (sequential)", QUERY_ROUNDS, || {
+        catalog.plugins_seq_iter()
+            .filter(|plugin| HasRelatedSet::<RequiresRelation, HostFeature>::seq_set_iter(plugin.info())
+                .any(|feature| feature == HostFeature::Options))
+            .count()
+    });
+    bench("implied plugin types (sequential)", QUERY_ROUNDS, || {
+        catalog.plugins_seq_iter()
+            .filter(|plugin| plugin.info().all_plugin_types().contains(PluginType::Filter))
+            .count()
+    });
+
+    #[cfg(feature = "rayon")]
+    {
+        bench("names (parallel)", QUERY_ROUNDS, || {
+            catalog.plugins_iter()
+                .map(|plugin| HasRelatedSet::<NameRelation, Literal>::set_iter(plugin.info()).count())
+                .sum()
+        });
+        bench("best name (parallel outer loop)", QUERY_ROUNDS, || {
+            catalog.plugins_iter()
+                .filter_map(|plugin| plugin.info().best_name(&langs))
+                .count()
+        });
+        bench("required feature check (parallel)", QUERY_ROUNDS, || {
+            catalog.plugins_iter()
+                .filter(|plugin| HasRelatedSet::<RequiresRelation, HostFeature>::set_iter(plugin.info())
+                    .any(|feature| feature == HostFeature::Options))
+                .count()
+        });
+        bench("implied plugin types (parallel)", QUERY_ROUNDS, || {
+            catalog.plugins_iter()
+                .filter(|plugin| plugin.info().all_plugin_types().contains(PluginType::Filter))
+                .count()
+        });
+    }
+}
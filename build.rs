@@ -0,0 +1,833 @@
+//! Generates constant types and implication data from the vendored LV2 specification data in
+//! `vendor/lv2` and the crate's own data in `data`.
+//!
+//! Each generated enum is described by an [`EnumSpec`]. Its variants are the resources of one kind
+//! (e.g. the subclasses of `lv2:Port` or the instances of `lv2:Feature`) defined in the spec's
+//! source files, in order of appearance. Variant names and docs come from `data/constants.ttl`,
+//! falling back to the local names and `rdfs:comment`s of the resources. The enums and their
+//! `iri_constant!` invocations are written to `constants.rs`, which is included by the `constants`
+//! module.
+//!
+//! Constant graphs ([`GraphSpec`]) and `const fn` lookups ([`MapSpec`]) over the generated enums
+//! are written to `implications.rs`, which is included by the `implications` module. Every
+//! statement using a predicate in the crate's own namespace must be read by an enum, graph or
+//! lookup, so that a misspelled or misplaced statement fails the build instead of being ignored.
+
+#[path = "build/turtle.rs"]
+mod turtle;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use turtle::{parse_turtle, Term, Triple, RDF_TYPE};
+
+const RDFS_SUBCLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+const RDFS_COMMENT: &str = "http://www.w3.org/2000/01/rdf-schema#comment";
+const OWL_DEPRECATED: &str = "http://www.w3.org/2002/07/owl#deprecated";
+
+const SE: &str = "https://github.com/allen-marshall/lv2-se-bundle#";
+const SE_NAME: &str = "https://github.com/allen-marshall/lv2-se-bundle#name";
+const SE_COMMENT: &str = "https://github.com/allen-marshall/lv2-se-bundle#comment";
+const SE_COMMENT_AS: &str = "https://github.com/allen-marshall/lv2-se-bundle#commentAs";
+const SE_ENUM: &str = "https://github.com/allen-marshall/lv2-se-bundle#enum";
+
+/// Maximum width of generated lines containing doc comments.
+const MAX_LINE_WIDTH: usize = 100;
+
+/// Data files read by the generator, relative to the crate root.
+const SOURCES: &[&str] = &[
+    "vendor/lv2/lv2core.ttl",
+    "vendor/lv2/atom.ttl",
+    "vendor/lv2/buf-size.ttl",
+    "vendor/lv2/data-access.ttl",
+    "vendor/lv2/instance-access.ttl",
+    "vendor/lv2/log.ttl",
+    "vendor/lv2/midi.ttl",
+    "vendor/lv2/morph.ttl",
+    "vendor/lv2/options.ttl",
+    "vendor/lv2/parameters.ttl",
+    "vendor/lv2/port-groups.ttl",
+    "vendor/lv2/port-props.ttl",
+    "vendor/lv2/resize-port.ttl",
+    "vendor/lv2/state.ttl",
+    "vendor/lv2/time.ttl",
+    "vendor/lv2/ui.ttl",
+    "vendor/lv2/units.ttl",
+    "vendor/lv2/urid.ttl",
+    "vendor/lv2/worker.ttl",
+    "data/constants.ttl",
+    "data/implications.ttl"
+];
+
+/// Rule selecting the resources that become variants of a generated enum.
+enum Members {
+    /// Direct and indirect subclasses of a class, excluding the class itself.
+    Subclasses(&'static str),
+
+    /// A class and its direct and indirect subclasses.
+    ClassAndSubclasses(&'static str),
+
+    /// Instances of a class or of any of its subclasses.
+    Instances(&'static str),
+
+    /// Instances of a class or of any of its subclasses, except for instances of a second class or
+    /// of any of its subclasses.
+    InstancesExcept(&'static str, &'static str)
+}
+
+/// Description of an enum to generate.
+struct EnumSpec {
+    /// Name of the enum type.
+    name: &'static str,
+
+    /// Doc comment for the enum type, one entry per line.
+    doc: &'static [&'static str],
+
+    /// Files (from [`SOURCES`]) defining the variants. Variants are ordered by file, then by first
+    /// appearance as a subject within the file, so new resources should be added after the
+    /// existing ones to keep the variant order stable.
+    sources: &'static [&'static str],
+
+    /// Rule selecting the resources that become variants.
+    members: Members,
+
+    /// IRIs of additional variants that the specification data doesn't classify in a way
+    /// [`members`](EnumSpec::members) can select.
+    extra_members: &'static [&'static str],
+
+    /// Suffix removed from local names to form variant names. Doesn't apply to variants with an
+    /// `se:name`.
+    suffix: &'static str,
+
+    /// Prefixes added to the names of variants from a namespace, as (namespace, prefix), unless
+    /// the name already starts with the prefix. Don't apply to variants with an `se:name`.
+    name_prefixes: &'static [(&'static str, &'static str)]
+}
+
+/// Description of a constant graph to generate. An edge (v0, v1) is added for each statement with
+/// the graph's predicate whose subject and object are variants of the node type.
+struct GraphSpec {
+    /// Name of the generated constant.
+    name: &'static str,
+
+    /// Doc comment for the constant.
+    doc: &'static str,
+
+    /// Name of the enum whose variants are the nodes.
+    node_type: &'static str,
+
+    /// IRI of the predicate defining the edges.
+    predicate: &'static str,
+
+    /// Whether each edge is also added in reverse.
+    symmetric: bool
+}
+
+/// Type of value returned by a generated lookup function.
+enum ValueKind {
+    /// `EnumSet` of the named enum, containing every object of the lookup's predicate.
+    Set(&'static str),
+
+    /// `Option` of the named enum, containing the object of the lookup's predicate.
+    Optional(&'static str),
+
+    /// `Option<f32>`, containing the numeric object of the lookup's predicate.
+    Number,
+
+    /// `Option<(f32, f32)>`, containing the numeric objects of the lookup's predicate and of the
+    /// specified predicate. A key must have both or neither.
+    Bounds(&'static str)
+}
+
+/// Description of a `const fn` lookup to generate, mapping each variant of an enum to values
+/// given by statements with the variant as the subject.
+struct MapSpec {
+    /// Name of the generated function.
+    name: &'static str,
+
+    /// Doc comment for the function.
+    doc: &'static str,
+
+    /// Name of the enum whose variants are the keys.
+    key_type: &'static str,
+
+    /// IRI of the predicate defining the values.
+    predicate: &'static str,
+
+    /// Type of value returned.
+    value: ValueKind
+}
+
+const ENUM_SPECS: &[EnumSpec] = &[
+    EnumSpec {
+        name: "PluginType",
+        doc: &[
+            "Identifiers for standard LV2 plugin classes.",
+            "",
+            "This type's implementations of [`Ord`](std::cmp::Ord) and",
+            "[`PartialOrd`](std::cmp::PartialOrd) have little semantic meaning, and exist mainly for use",
+            "with collections that require an ordered element type. In particular, superclasses are not",
+            "guaranteed to have a particular ordering relative to their subclasses."
+        ],
+        sources: &["vendor/lv2/lv2core.ttl"],
+        members: Members::Subclasses("http://lv2plug.in/ns/lv2core#Plugin"),
+        extra_members: &[],
+        suffix: "Plugin",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "PortType",
+        doc: &["Identifiers for standard LV2 port classes."],
+        sources: &["vendor/lv2/lv2core.ttl", "vendor/lv2/atom.ttl", "vendor/lv2/morph.ttl"],
+        members: Members::Subclasses("http://lv2plug.in/ns/lv2core#Port"),
+        extra_members: &[],
+        suffix: "Port",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "HostFeature",
+        doc: &[
+            "Identifiers for standard host features that can be supported and/or required by a plugin or",
+            "LV2 UI. Does not include UI port protocol features; for those, see",
+            "[`UiPortProtocol`](self::UiPortProtocol)."
+        ],
+        sources: &[
+            "vendor/lv2/lv2core.ttl",
+            "vendor/lv2/buf-size.ttl",
+            "vendor/lv2/log.ttl",
+            "vendor/lv2/options.ttl",
+            "vendor/lv2/port-props.ttl",
+            "vendor/lv2/resize-port.ttl",
+            "vendor/lv2/state.ttl",
+            "vendor/lv2/ui.ttl",
+            "vendor/lv2/urid.ttl",
+            "vendor/lv2/worker.ttl",
+            "vendor/lv2/data-access.ttl",
+            "vendor/lv2/instance-access.ttl"
+        ],
+        // UI port protocols are features too, but have their own type.
+        members: Members::InstancesExcept(
+            "http://lv2plug.in/ns/lv2core#Feature",
+            "http://lv2plug.in/ns/extensions/ui#PortProtocol"
+        ),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "UiPortProtocol",
+        doc: &[
+            "Identifiers for standard host features that represent LV2 port protocols used by LV2 UIs. A",
+            "port protocol defines a way for the LV2 UI and plugin to communicate port values."
+        ],
+        sources: &["vendor/lv2/atom.ttl", "vendor/lv2/ui.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/extensions/ui#PortProtocol"),
+        extra_members: &[],
+        suffix: "Protocol",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "Lv2Option",
+        doc: &["Identifiers for standard LV2 options that may be set by a host."],
+        sources: &["vendor/lv2/buf-size.ttl", "vendor/lv2/parameters.ttl", "vendor/lv2/ui.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/ext/options#Option"),
+        // param:sampleRate is declared as a parameter, but is also passed as an option.
+        extra_members: &["http://lv2plug.in/ns/ext/parameters#sampleRate"],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "ExtensionData",
+        doc: &["Identifiers for standard extension interfaces that a plugin or LV2 UI can provide."],
+        sources: &["vendor/lv2/options.ttl", "vendor/lv2/state.ttl", "vendor/lv2/ui.ttl", "vendor/lv2/worker.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/lv2core#ExtensionData"),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "AtomType",
+        doc: &[
+            "Identifiers for standard LV2 atom classes. Non-standard atom classes can exist but are not",
+            "represented by this type.",
+            "",
+            "This type's implementations of [`Ord`](std::cmp::Ord) and",
+            "[`PartialOrd`](std::cmp::PartialOrd) have little semantic meaning, and exist mainly for use",
+            "with collections that require an ordered element type. In particular, superclasses are not",
+            "guaranteed to have a particular ordering relative to their subclasses."
+        ],
+        sources: &["vendor/lv2/atom.ttl", "vendor/lv2/midi.ttl"],
+        members: Members::ClassAndSubclasses("http://lv2plug.in/ns/ext/atom#Atom"),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[("http://lv2plug.in/ns/ext/midi#", "Midi")]
+    },
+    EnumSpec {
+        name: "Unit",
+        doc: &["Identifiers for measurement units defined by the LV2 standard."],
+        sources: &["vendor/lv2/units.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/extensions/units#Unit"),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "PortProperty",
+        doc: &["Identifiers for standard LV2 port properties that can apply to a port."],
+        sources: &["vendor/lv2/lv2core.ttl", "vendor/lv2/port-props.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/lv2core#PortProperty"),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "PortDesignation",
+        doc: &[
+            "Identifiers for standard LV2 'designations' that can apply to a port. Does not include",
+            "channel designations; for those, see [`PortChannel`](self::PortChannel)."
+        ],
+        sources: &["vendor/lv2/lv2core.ttl", "vendor/lv2/parameters.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/lv2core#Designation"),
+        // lv2:latency is declared as a plain property, but is used as a designation.
+        extra_members: &["http://lv2plug.in/ns/lv2core#latency"],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "TimeDesignation",
+        doc: &[
+            "Identifiers for standard designations from the LV2 time extension that can apply to a port.",
+            "Each one designates the port as receiving (or sending) one property of the transport position,",
+            "so that a host can keep tempo-synced plugins up to date without sending full",
+            "`time:Position` objects."
+        ],
+        sources: &["vendor/lv2/time.ttl"],
+        members: Members::Instances("http://www.w3.org/2002/07/owl#DatatypeProperty"),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[]
+    },
+    EnumSpec {
+        name: "PortChannel",
+        doc: &["Identifiers for standard LV2 channel designations that can apply to a port."],
+        sources: &["vendor/lv2/port-groups.ttl"],
+        members: Members::Instances("http://lv2plug.in/ns/lv2core#Channel"),
+        extra_members: &[],
+        suffix: "",
+        name_prefixes: &[]
+    }
+];
+
+const GRAPH_SPECS: &[GraphSpec] = &[
+    GraphSpec {
+        name: "PLUGIN_TYPE_SUBCLASSES",
+        doc: "Direct subclass relationships among \
+            [`PluginType`](crate::bundle_model::constants::PluginType)s. If an edge (c0, c1) exists in \
+            the graph, then c0 is declared a direct subclass of c1.",
+        node_type: "PluginType",
+        predicate: RDFS_SUBCLASS_OF,
+        symmetric: false
+    },
+    GraphSpec {
+        name: "HOST_FEATURE_IMPLICATIONS",
+        doc: "Direct implications among host features. If an edge (f0, f1) exists in the graph, then an \
+            entity that requires f0 effectively requires f1 as well.",
+        node_type: "HostFeature",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#impliesFeature",
+        symmetric: false
+    },
+    GraphSpec {
+        name: "PORT_PROPERTY_IMPLICATIONS",
+        doc: "Direct implications among port properties. If an edge (p0, p1) exists in the graph, then a \
+            port with property p0 should be treated as also having property p1.",
+        node_type: "PortProperty",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#impliesPortProperty",
+        symmetric: false
+    },
+    GraphSpec {
+        name: "PORT_PROPERTY_CONFLICTS",
+        doc: "Conflicts among port properties. If an edge (p0, p1) exists in the graph, then a port with \
+            property p0 should not be treated as having property p1 unless p1 is stated explicitly. The \
+            graph is symmetric.",
+        node_type: "PortProperty",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#conflictsWith",
+        symmetric: true
+    }
+];
+
+const MAP_SPECS: &[MapSpec] = &[
+    MapSpec {
+        name: "host_features_required_by_extension_data",
+        doc: "Gets the host features that a plugin or UI providing an LV2 extension data interface needs \
+            from the host in order for the interface to be usable.",
+        key_type: "ExtensionData",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#requiresFeature",
+        value: ValueKind::Set("HostFeature")
+    },
+    MapSpec {
+        name: "unit_implied_by_designation",
+        doc: "Gets the port unit implied by an LV2 port designation, if any.",
+        key_type: "PortDesignation",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#impliesUnit",
+        value: ValueKind::Optional("Unit")
+    },
+    MapSpec {
+        name: "port_properties_implied_by_designation",
+        doc: "Gets the port properties implied by an LV2 port designation.",
+        key_type: "PortDesignation",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#impliesPortProperty",
+        value: ValueKind::Set("PortProperty")
+    },
+    MapSpec {
+        name: "bounds_implied_by_designation",
+        doc: "Gets the (minimum, maximum) bounds implied by an LV2 port designation for the port's value, \
+            if any.",
+        key_type: "PortDesignation",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#impliesMinimum",
+        value: ValueKind::Bounds("https://github.com/allen-marshall/lv2-se-bundle#impliesMaximum")
+    },
+    MapSpec {
+        name: "default_implied_by_designation",
+        doc: "Gets the default port value implied by an LV2 port designation, if any.",
+        key_type: "PortDesignation",
+        predicate: "https://github.com/allen-marshall/lv2-se-bundle#impliesDefault",
+        value: ValueKind::Number
+    }
+];
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build/turtle.rs");
+    let mut files = BTreeMap::new();
+    for &source in SOURCES {
+        println!("cargo:rerun-if-changed={}", source);
+        let text = fs::read_to_string(source)
+            .unwrap_or_else(|err| panic!("cannot read {}: {}", source, err));
+        let triples = parse_turtle(&text)
+            .unwrap_or_else(|err| panic!("cannot parse {}: {}", source, err));
+        files.insert(source, triples);
+    }
+    let data = Data::new(&files);
+
+    let mut used = BTreeSet::new();
+    let enums: Vec<Enum> = ENUM_SPECS.iter().map(|spec| Enum::new(spec, &data, &mut used)).collect();
+    let mut constants = String::from("// Generated by build.rs from the vendored LV2 specification data. Do not edit.\n");
+    for generated in &enums {
+        writeln!(constants).unwrap();
+        generated.write(&mut constants);
+    }
+    write_output(&out_dir, "constants.rs", &constants);
+
+    let mut implications = String::from("// Generated by build.rs from the vendored LV2 specification data and \
+        data/implications.ttl. Do not edit.\n");
+    for spec in GRAPH_SPECS {
+        writeln!(implications).unwrap();
+        write_graph(spec, &enums, &data, &mut used, &mut implications);
+    }
+    for spec in MAP_SPECS {
+        writeln!(implications).unwrap();
+        write_map(spec, &enums, &data, &mut used, &mut implications);
+    }
+    for (index, triple) in data.triples.iter().enumerate() {
+        if triple.predicate.starts_with(SE) && !used.contains(&index) {
+            panic!("statement not used by any enum, graph or lookup: {:?}", triple);
+        }
+    }
+    write_output(&out_dir, "implications.rs", &implications);
+}
+
+fn write_output(out_dir: &str, name: &str, code: &str) {
+    fs::write(Path::new(out_dir).join(name), code)
+        .unwrap_or_else(|err| panic!("cannot write {}: {}", name, err));
+}
+
+/// Statements from all data files, indexed for the generator.
+struct Data<'a> {
+    /// All statements, in file order.
+    triples: Vec<&'a Triple>,
+
+    /// IRI subjects of each file, in order of first appearance.
+    subjects: BTreeMap<&'a str, Vec<&'a str>>,
+
+    /// Direct superclasses of each class.
+    superclasses: BTreeMap<&'a str, Vec<&'a str>>,
+
+    /// Direct types of each resource.
+    types: BTreeMap<&'a str, Vec<&'a str>>,
+
+    /// First `rdfs:comment` of each resource.
+    comments: BTreeMap<&'a str, &'a str>,
+
+    /// Resources marked with `owl:deprecated true`.
+    deprecated: BTreeSet<&'a str>
+}
+
+impl<'a> Data<'a> {
+    fn new(files: &'a BTreeMap<&'static str, Vec<Triple>>) -> Self {
+        let mut data = Data {
+            triples: Vec::new(),
+            subjects: BTreeMap::new(),
+            superclasses: BTreeMap::new(),
+            types: BTreeMap::new(),
+            comments: BTreeMap::new(),
+            deprecated: BTreeSet::new()
+        };
+        for &source in SOURCES {
+            let subjects = data.subjects.entry(source).or_default();
+            for triple in &files[source] {
+                data.triples.push(triple);
+                if let Term::Iri(subject) = &triple.subject {
+                    if !subjects.contains(&subject.as_str()) {
+                        subjects.push(subject);
+                    }
+                    match (triple.predicate.as_str(), &triple.object) {
+                        (RDFS_SUBCLASS_OF, Term::Iri(superclass)) => {
+                            data.superclasses.entry(subject).or_default().push(superclass);
+                        },
+                        (RDF_TYPE, Term::Iri(class)) => {
+                            data.types.entry(subject).or_default().push(class);
+                        },
+                        (RDFS_COMMENT, Term::Literal(comment)) => {
+                            data.comments.entry(subject).or_insert(comment);
+                        },
+                        (OWL_DEPRECATED, Term::Literal(value)) if value == "true" => {
+                            data.deprecated.insert(subject);
+                        },
+                        _ => {}
+                    }
+                }
+            }
+        }
+        data
+    }
+
+    /// Checks if a class is a direct or indirect subclass of another class.
+    fn is_subclass_of(&self, class: &str, superclass: &str) -> bool {
+        let mut visited = Vec::new();
+        let mut stack = vec![class];
+        while let Some(current) = stack.pop() {
+            for &next in self.superclasses.get(current).into_iter().flatten() {
+                if next == superclass {
+                    return true;
+                }
+                if !visited.contains(&next) {
+                    visited.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    /// Checks if a resource is an instance of a class, directly or through a subclass.
+    fn is_instance_of(&self, resource: &str, class: &str) -> bool {
+        self.types.get(resource).into_iter().flatten()
+            .any(|&direct| direct == class || self.is_subclass_of(direct, class))
+    }
+
+    /// Checks if a resource is selected by a membership rule.
+    fn is_member(&self, resource: &str, members: &Members) -> bool {
+        match *members {
+            Members::Subclasses(class) => self.is_subclass_of(resource, class),
+            Members::ClassAndSubclasses(class) => resource == class || self.is_subclass_of(resource, class),
+            Members::Instances(class) => self.is_instance_of(resource, class),
+            Members::InstancesExcept(class, excluded) =>
+                self.is_instance_of(resource, class) && !self.is_instance_of(resource, excluded)
+        }
+    }
+
+    /// Gets the objects of the statements with a subject and predicate. The indices of the
+    /// statements are added to `used`.
+    fn objects(&self, subject: &Term, predicate: &str, used: &mut BTreeSet<usize>) -> Vec<&'a Term> {
+        self.triples.iter().enumerate()
+            .filter(|(_, triple)| triple.predicate == predicate && triple.subject == *subject)
+            .map(|(index, triple)| {
+                used.insert(index);
+                &triple.object
+            })
+            .collect()
+    }
+
+    /// Gets the only literal object of the statements with a subject and predicate, if any. The
+    /// indices of the statements are added to `used`.
+    fn literal(&self, subject: &Term, predicate: &str, used: &mut BTreeSet<usize>) -> Option<&'a str> {
+        match self.objects(subject, predicate, used)[..] {
+            [] => None,
+            [Term::Literal(value)] => Some(value),
+            ref objects => panic!("{:?} needs a single literal {}, found {:?}", subject, predicate, objects)
+        }
+    }
+}
+
+/// An enum to generate, with its variants resolved.
+struct Enum<'a> {
+    spec: &'static EnumSpec,
+
+    /// IRI, name and doc comment of each variant.
+    variants: Vec<(&'a str, String, Option<String>)>
+}
+
+impl<'a> Enum<'a> {
+    /// Resolves the variants of an enum. The indices of the statements read are added to `used`.
+    fn new(spec: &'static EnumSpec, data: &Data<'a>, used: &mut BTreeSet<usize>) -> Self {
+        let mut iris: Vec<&str> = Vec::new();
+        for source in spec.sources {
+            let subjects = data.subjects.get(source)
+                .unwrap_or_else(|| panic!("{}: {} is not in SOURCES", spec.name, source));
+            for &subject in subjects {
+                let is_variant = data.is_member(subject, &spec.members) || spec.extra_members.contains(&subject);
+                if is_variant && !iris.contains(&subject) {
+                    iris.push(subject);
+                }
+            }
+        }
+        for extra_member in spec.extra_members {
+            assert!(iris.contains(extra_member), "{}: extra member {} not found", spec.name, extra_member);
+        }
+
+        let names: Vec<String> = iris.iter()
+            .map(|iri| data.literal(&Term::Iri(iri.to_string()), SE_NAME, used)
+                .map_or_else(|| variant_name(spec, iri), str::to_string))
+            .collect();
+        for (index, name) in names.iter().enumerate() {
+            let is_identifier = name.starts_with(|c: char| c.is_ascii_uppercase())
+                && name.chars().all(|c| c.is_ascii_alphanumeric());
+            assert!(is_identifier, "{}: invalid variant name {} for {}", spec.name, name, iris[index]);
+            assert!(!names[..index].contains(name), "{}: duplicate variant name {}", spec.name, name);
+        }
+        for (namespace, _) in spec.name_prefixes {
+            assert!(iris.iter().any(|iri| iri.starts_with(namespace)), "{}: {} matches no variant", spec.name,
+                namespace);
+        }
+
+        let variants = iris.iter().zip(&names)
+            .map(|(&iri, name)| {
+                let subject = Term::Iri(iri.to_string());
+                // Marks the se:enum statements of other enums as used too, but their se:comment
+                // statements stay unused unless the named enum reads them.
+                let enum_node = data.objects(&subject, SE_COMMENT_AS, used).into_iter()
+                    .find(|node| data.literal(node, SE_ENUM, used) == Some(spec.name));
+                let enum_doc = enum_node.map(|node| data.literal(node, SE_COMMENT, used)
+                    .unwrap_or_else(|| panic!("{}: se:commentAs of {} has no se:comment", spec.name, iri)));
+                let doc = enum_doc
+                    .or_else(|| data.literal(&subject, SE_COMMENT, used))
+                    .or_else(|| data.comments.get(iri).copied())
+                    .map(|doc| link_variants(spec.name, doc, &names))
+                    .map(|doc| if data.deprecated.contains(iri) {
+                        format!("{} Deprecated by the LV2 specification.", doc)
+                    }
+                    else {
+                        doc
+                    });
+                (iri, name.clone(), doc)
+            })
+            .collect();
+        Enum { spec, variants }
+    }
+
+    /// Gets the name of the variant identified by an IRI, if any.
+    fn variant(&self, iri: &str) -> Option<&str> {
+        self.variants.iter()
+            .find(|(variant_iri, _, _)| *variant_iri == iri)
+            .map(|(_, name, _)| name.as_str())
+    }
+
+    /// Writes the enum and its `iri_constant!` invocation.
+    fn write(&self, code: &mut String) {
+        for line in self.spec.doc {
+            writeln!(code, "///{}{}", if line.is_empty() { "" } else { " " }, line).unwrap();
+        }
+        writeln!(code, "#[derive(Debug, PartialOrd, Ord, Hash, EnumSetType, Enum)]").unwrap();
+        writeln!(code, "pub enum {} {{", self.spec.name).unwrap();
+        for (index, (_, name, doc)) in self.variants.iter().enumerate() {
+            if let Some(doc) = doc {
+                for line in wrap_doc(doc, "    /// ") {
+                    writeln!(code, "{}", line).unwrap();
+                }
+            }
+            let separator = if index + 1 < self.variants.len() { "," } else { "" };
+            writeln!(code, "    {}{}", name, separator).unwrap();
+        }
+        writeln!(code, "}}").unwrap();
+        writeln!(code).unwrap();
+
+        writeln!(code, "iri_constant!({} {{", self.spec.name).unwrap();
+        for (index, (iri, name, _)) in self.variants.iter().enumerate() {
+            let separator = if index + 1 < self.variants.len() { "," } else { "" };
+            writeln!(code, "    {} => \"{}\"{}", name, iri, separator).unwrap();
+        }
+        writeln!(code, "}});").unwrap();
+    }
+}
+
+/// Finds a generated enum by name.
+fn find_enum<'e, 'a>(enums: &'e [Enum<'a>], name: &str) -> &'e Enum<'a> {
+    enums.iter().find(|generated| generated.spec.name == name)
+        .unwrap_or_else(|| panic!("no enum spec named {}", name))
+}
+
+/// Writes a constant graph. The indices of the statements read are added to `used`.
+fn write_graph(spec: &GraphSpec, enums: &[Enum], data: &Data, used: &mut BTreeSet<usize>, code: &mut String) {
+    let nodes = find_enum(enums, spec.node_type);
+    let mut edges: Vec<(&str, &str)> = Vec::new();
+    for (index, triple) in data.triples.iter().enumerate() {
+        if triple.predicate != spec.predicate {
+            continue;
+        }
+        let edge = match (&triple.subject, &triple.object) {
+            (Term::Iri(subject), Term::Iri(object)) => nodes.variant(subject).zip(nodes.variant(object)),
+            _ => None
+        };
+        if let Some((from, to)) = edge {
+            used.insert(index);
+            let reverse = if spec.symmetric { Some((to, from)) } else { None };
+            for edge in Some((from, to)).into_iter().chain(reverse) {
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
+    }
+
+    for line in wrap_doc(spec.doc, "/// ") {
+        writeln!(code, "{}", line).unwrap();
+    }
+    writeln!(code, "pub(crate) const {}: crate::enum_graph::EnumSetDiGraph<{}> = enum_graph!({} {{",
+        spec.name, spec.node_type, spec.node_type).unwrap();
+    for (index, (from, to)) in edges.iter().enumerate() {
+        let separator = if index + 1 < edges.len() { "," } else { "" };
+        writeln!(code, "    {} => {}{}", from, to, separator).unwrap();
+    }
+    writeln!(code, "}});").unwrap();
+}
+
+/// Writes a `const fn` lookup. The indices of the statements read are added to `used`.
+fn write_map(spec: &MapSpec, enums: &[Enum], data: &Data, used: &mut BTreeSet<usize>, code: &mut String) {
+    let keys = find_enum(enums, spec.key_type);
+    let variant = |value_type: &str, term: &Term| match term {
+        Term::Iri(iri) => format!("{}::{}", value_type, find_enum(enums, value_type).variant(iri)
+            .unwrap_or_else(|| panic!("{}: {} is not a {}", spec.name, iri, value_type))),
+        other => panic!("{}: {:?} is not a {}", spec.name, other, value_type)
+    };
+    let number = |term: &Term| match term {
+        Term::Literal(value) => value.parse::<f32>().ok().map(|value| format!("{:?}", value)),
+        _ => None
+    }.unwrap_or_else(|| panic!("{}: {:?} is not a number", spec.name, term));
+    let single = |terms: Vec<&Term>, key_iri: &str| {
+        assert!(terms.len() <= 1, "{}: {} has more than one value", spec.name, key_iri);
+        terms.first().map(|term| (*term).clone())
+    };
+
+    let (value_type, default) = match spec.value {
+        ValueKind::Set(value_type) => (format!("EnumSet<{}>", value_type), "enum_set!()"),
+        ValueKind::Optional(value_type) => (format!("Option<{}>", value_type), "None"),
+        ValueKind::Number => (String::from("Option<f32>"), "None"),
+        ValueKind::Bounds(_) => (String::from("Option<(f32, f32)>"), "None")
+    };
+    let mut arms = Vec::new();
+    for (key_iri, key, _) in &keys.variants {
+        let key_term = Term::Iri(key_iri.to_string());
+        let found = data.objects(&key_term, spec.predicate, used);
+        let value = match spec.value {
+            ValueKind::Set(value_type) if !found.is_empty() => {
+                let members: Vec<String> = found.iter().map(|term| variant(value_type, term)).collect();
+                Some(format!("enum_set!({})", members.join(" | ")))
+            },
+            ValueKind::Set(_) => None,
+            ValueKind::Optional(value_type) => single(found, key_iri)
+                .map(|term| format!("Some({})", variant(value_type, &term))),
+            ValueKind::Number => single(found, key_iri).map(|term| format!("Some({})", number(&term))),
+            ValueKind::Bounds(maximum_predicate) => {
+                let maximum = data.objects(&key_term, maximum_predicate, used);
+                match (single(found, key_iri), single(maximum, key_iri)) {
+                    (Some(minimum), Some(maximum)) =>
+                        Some(format!("Some(({}, {}))", number(&minimum), number(&maximum))),
+                    (None, None) => None,
+                    _ => panic!("{}: {} has only one bound", spec.name, key_iri)
+                }
+            }
+        };
+        if let Some(value) = value {
+            arms.push(format!("{}::{} => {}", spec.key_type, key, value));
+        }
+    }
+    if arms.len() < keys.variants.len() {
+        arms.push(format!("_ => {}", default));
+    }
+
+    for line in wrap_doc(spec.doc, "/// ") {
+        writeln!(code, "{}", line).unwrap();
+    }
+    writeln!(code, "pub const fn {}({}: {}) -> {} {{", spec.name, snake_case(spec.key_type), spec.key_type,
+        value_type).unwrap();
+    writeln!(code, "    match {} {{", snake_case(spec.key_type)).unwrap();
+    for (index, arm) in arms.iter().enumerate() {
+        let separator = if index + 1 < arms.len() { "," } else { "" };
+        writeln!(code, "        {}{}", arm, separator).unwrap();
+    }
+    writeln!(code, "    }}").unwrap();
+    writeln!(code, "}}").unwrap();
+}
+
+/// Derives the variant name for an IRI without an `se:name`.
+fn variant_name(spec: &EnumSpec, iri: &str) -> String {
+    let local_name = iri.rsplit(['#', '/']).next().unwrap_or(iri);
+    let stem = local_name.strip_suffix(spec.suffix).filter(|stem| !stem.is_empty()).unwrap_or(local_name);
+    let mut chars = stem.chars();
+    let name: String = chars.next().map(|first| first.to_ascii_uppercase()).into_iter().chain(chars).collect();
+    match spec.name_prefixes.iter().find(|(namespace, _)| iri.starts_with(namespace)) {
+        Some((_, prefix)) if !name.starts_with(prefix) => format!("{}{}", prefix, name),
+        _ => name
+    }
+}
+
+/// Converts a type name to a snake case parameter name.
+fn snake_case(name: &str) -> String {
+    let mut output = String::new();
+    for c in name.chars() {
+        if c.is_ascii_uppercase() && !output.is_empty() {
+            output.push('_');
+        }
+        output.push(c.to_ascii_lowercase());
+    }
+    output
+}
+
+/// Turns code spans naming variants of the enum being generated into links to those variants.
+fn link_variants(enum_name: &str, doc: &str, names: &[String]) -> String {
+    let mut output = String::new();
+    for (index, part) in doc.split('`').enumerate() {
+        // Odd parts are inside code spans.
+        if index % 2 == 0 {
+            output.push_str(part);
+        }
+        else if names.iter().any(|name| name == part) {
+            write!(output, "[`{}`](self::{}::{})", part, enum_name, part).unwrap();
+        }
+        else {
+            write!(output, "`{}`", part).unwrap();
+        }
+    }
+    output
+}
+
+/// Formats text as doc comment lines no wider than [`MAX_LINE_WIDTH`].
+fn wrap_doc(text: &str, prefix: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = prefix.to_string();
+    for word in text.split_whitespace() {
+        if line.len() > prefix.len() && line.len() + 1 + word.len() > MAX_LINE_WIDTH {
+            lines.push(line);
+            line = prefix.to_string();
+        }
+        if line.len() > prefix.len() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines
+}
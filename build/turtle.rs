@@ -0,0 +1,531 @@
+//! Turtle parser for the vendored LV2 specification data.
+//!
+//! Covers the Turtle constructs used by the LV2 specifications: `@prefix`/`@base` directives and
+//! their SPARQL-style forms, prefixed names and (relative) IRI references, the `a` keyword,
+//! predicate and object lists (`;` and `,`), blank node labels and property lists (`[ ... ]`),
+//! collections (`( ... )`, expanded to `rdf:first`/`rdf:rest` lists), short and long string
+//! literals (with language tags and datatypes discarded), and boolean and numeric literals.
+//! Anything else, such as unknown directives, N3 formulas or unknown string escapes, is reported as
+//! an error rather than skipped, so that data the code generator would silently misread fails the
+//! build instead.
+
+use std::collections::BTreeMap;
+
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+pub const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+pub const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+pub const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// RDF term. Literal datatypes and language tags are discarded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Iri(String),
+    Blank(String),
+    Literal(String)
+}
+
+/// RDF statement. Predicates are always IRIs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Triple {
+    pub subject: Term,
+    pub predicate: String,
+    pub object: Term
+}
+
+/// Turtle token.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// `<...>`, with the brackets removed.
+    IriRef(String),
+
+    /// Prefixed name, keyword (`a`, `true`, `false`) or number.
+    Name(String),
+
+    /// String literal, with escapes resolved and any language tag or datatype skipped.
+    Literal(String),
+
+    /// `@prefix` or `@base`.
+    Directive(String),
+
+    /// One of `. ; , [ ] ( )`.
+    Punct(char)
+}
+
+/// Splits Turtle text into tokens.
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+        }
+        else if c == '#' {
+            while pos < chars.len() && chars[pos] != '\n' {
+                pos += 1;
+            }
+        }
+        else if c == '<' {
+            let end = (pos..chars.len()).find(|&i| chars[i] == '>').ok_or("unterminated IRI")?;
+            tokens.push(Token::IriRef(chars[pos + 1..end].iter().collect()));
+            pos = end + 1;
+        }
+        else if c == '"' || c == '\'' {
+            let long = chars[pos..].starts_with(&[c, c, c]);
+            let delimiter_len = if long { 3 } else { 1 };
+            pos += delimiter_len;
+            let mut value = String::new();
+            loop {
+                if pos >= chars.len() {
+                    return Err(String::from("unterminated string literal"));
+                }
+                if (long && chars[pos..].starts_with(&[c, c, c])) || (!long && chars[pos] == c) {
+                    pos += delimiter_len;
+                    break;
+                }
+                if chars[pos] == '\\' {
+                    value.push(unescape(&chars, &mut pos)?);
+                }
+                else if !long && chars[pos] == '\n' {
+                    return Err(String::from("line break in short string literal"));
+                }
+                else {
+                    value.push(chars[pos]);
+                    pos += 1;
+                }
+            }
+            if pos < chars.len() && chars[pos] == '@' {
+                pos += 1;
+                while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '-') {
+                    pos += 1;
+                }
+            }
+            else if chars[pos..].starts_with(&['^', '^']) {
+                pos += 2;
+                // The datatype is read as a separate token and dropped below.
+                tokens.push(Token::Literal(value));
+                let datatype = tokenize_one(&chars, &mut pos)?;
+                if !matches!(datatype, Token::IriRef(_) | Token::Name(_)) {
+                    return Err(String::from("invalid literal datatype"));
+                }
+                continue;
+            }
+            tokens.push(Token::Literal(value));
+        }
+        else if c == '@' {
+            pos += 1;
+            let start = pos;
+            while pos < chars.len() && chars[pos].is_alphabetic() {
+                pos += 1;
+            }
+            tokens.push(Token::Directive(chars[start..pos].iter().collect()));
+        }
+        else if ".;,[]()".contains(c) {
+            tokens.push(Token::Punct(c));
+            pos += 1;
+        }
+        else {
+            tokens.push(tokenize_one(&chars, &mut pos)?);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Reads an escape sequence in a string literal starting at the specified position (which holds
+/// the backslash), and returns the escaped character.
+fn unescape(chars: &[char], pos: &mut usize) -> Result<char, String> {
+    let escape = chars.get(*pos + 1).copied().ok_or("unterminated string literal")?;
+    *pos += 2;
+    let hex_len = match escape {
+        't' => return Ok('\t'),
+        'b' => return Ok('\u{8}'),
+        'n' => return Ok('\n'),
+        'r' => return Ok('\r'),
+        'f' => return Ok('\u{c}'),
+        '"' | '\'' | '\\' => return Ok(escape),
+        'u' => 4,
+        'U' => 8,
+        other => return Err(format!("unknown string escape '\\{}'", other))
+    };
+    let digits: String = chars.get(*pos..*pos + hex_len).ok_or("truncated string escape")?.iter().collect();
+    *pos += hex_len;
+    u32::from_str_radix(&digits, 16).ok()
+        .and_then(std::char::from_u32)
+        .ok_or_else(|| format!("invalid string escape '\\{}{}'", escape, digits))
+}
+
+/// Reads an IRI reference or name token starting at the specified position.
+fn tokenize_one(chars: &[char], pos: &mut usize) -> Result<Token, String> {
+    if *pos < chars.len() && chars[*pos] == '<' {
+        let end = (*pos..chars.len()).find(|&i| chars[i] == '>').ok_or("unterminated IRI")?;
+        let iri = chars[*pos + 1..end].iter().collect();
+        *pos = end + 1;
+        return Ok(Token::IriRef(iri));
+    }
+    let is_name_char = |c: char| c.is_alphanumeric() || "_-.:+".contains(c);
+    let start = *pos;
+    while *pos < chars.len() && is_name_char(chars[*pos]) {
+        *pos += 1;
+    }
+    // A trailing '.' terminates the statement rather than belonging to the name.
+    while *pos > start && chars[*pos - 1] == '.' {
+        *pos -= 1;
+    }
+    if *pos == start {
+        return Err(format!("unexpected character '{}'", chars.get(*pos).copied().unwrap_or(' ')));
+    }
+    Ok(Token::Name(chars[start..*pos].iter().collect()))
+}
+
+/// Parses Turtle text into triples.
+pub fn parse_turtle(text: &str) -> Result<Vec<Triple>, String> {
+    let mut parser = Parser {
+        tokens: tokenize(text)?,
+        pos: 0,
+        base: None,
+        prefixes: BTreeMap::new(),
+        next_blank: 0,
+        triples: Vec::new()
+    };
+    while parser.pos < parser.tokens.len() {
+        parser.statement()?;
+    }
+    Ok(parser.triples)
+}
+
+/// Recursive descent parser over Turtle tokens.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    base: Option<String>,
+    prefixes: BTreeMap<String, String>,
+    next_blank: usize,
+    triples: Vec<Triple>
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self.tokens.get(self.pos).cloned().ok_or("unexpected end of input")?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, punct: char) -> Result<(), String> {
+        match self.next()? {
+            Token::Punct(c) if c == punct => Ok(()),
+            other => Err(format!("expected '{}', found {:?}", punct, other))
+        }
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.pos += 1;
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    fn statement(&mut self) -> Result<(), String> {
+        // `@prefix` and `@base` end with '.', while the SPARQL-style `PREFIX` and `BASE` don't.
+        let directive = match self.peek() {
+            Some(Token::Directive(directive)) => Some((directive.clone(), true)),
+            Some(Token::Name(name)) if name.eq_ignore_ascii_case("prefix") || name.eq_ignore_ascii_case("base") =>
+                Some((name.to_ascii_lowercase(), false)),
+            _ => None
+        };
+        if let Some((directive, dotted)) = directive {
+            self.pos += 1;
+            match (directive.as_str(), self.next()?) {
+                ("prefix", Token::Name(prefix)) if prefix.ends_with(':') => {
+                    let iri = match self.next()? {
+                        Token::IriRef(iri) => self.resolve(iri),
+                        other => return Err(format!("expected IRI, found {:?}", other))
+                    };
+                    self.prefixes.insert(prefix[..prefix.len() - 1].to_string(), iri);
+                },
+                ("base", Token::IriRef(iri)) => {
+                    self.base = Some(self.resolve(iri));
+                },
+                other => return Err(format!("unsupported directive {:?}", other))
+            }
+            return if dotted { self.expect('.') } else { Ok(()) };
+        }
+        let subject = self.subject()?;
+        if !(matches!(subject, Term::Blank(_)) && self.peek() == Some(&Token::Punct('.'))) {
+            self.predicate_object_list(&subject)?;
+        }
+        self.expect('.')
+    }
+
+    fn subject(&mut self) -> Result<Term, String> {
+        match self.next()? {
+            Token::Punct('[') => self.blank_node_property_list(),
+            Token::Punct('(') => self.collection(),
+            token => self.node(token)
+        }
+    }
+
+    fn predicate_object_list(&mut self, subject: &Term) -> Result<(), String> {
+        loop {
+            let predicate = match self.next()? {
+                Token::Name(ref name) if name == "a" => RDF_TYPE.to_string(),
+                token => self.resource(token)?
+            };
+            loop {
+                let object = self.object()?;
+                self.triples.push(Triple { subject: subject.clone(), predicate: predicate.clone(), object });
+                if !self.eat(',') {
+                    break;
+                }
+            }
+            if !self.eat(';') {
+                return Ok(());
+            }
+            // Turtle allows repeated and trailing semicolons.
+            while self.eat(';') {}
+            match self.peek() {
+                Some(Token::Punct('.')) | Some(Token::Punct(']')) => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Term, String> {
+        match self.next()? {
+            Token::Literal(value) => Ok(Term::Literal(value)),
+            Token::Punct('[') => self.blank_node_property_list(),
+            Token::Punct('(') => self.collection(),
+            Token::Name(ref name) if is_bare_literal(name) => Ok(Term::Literal(name.clone())),
+            token => self.node(token)
+        }
+    }
+
+    fn blank_node_property_list(&mut self) -> Result<Term, String> {
+        let node = self.new_blank();
+        if !self.eat(']') {
+            self.predicate_object_list(&node)?;
+            self.expect(']')?;
+        }
+        Ok(node)
+    }
+
+    /// Parses the rest of a collection (after the opening parenthesis) into an `rdf:first` /
+    /// `rdf:rest` list, and returns the head of the list.
+    fn collection(&mut self) -> Result<Term, String> {
+        let mut items = Vec::new();
+        while !self.eat(')') {
+            items.push(self.object()?);
+        }
+        let mut head = Term::Iri(RDF_NIL.to_string());
+        for item in items.into_iter().rev() {
+            let node = self.new_blank();
+            self.triples.push(Triple { subject: node.clone(), predicate: RDF_FIRST.to_string(), object: item });
+            self.triples.push(Triple { subject: node.clone(), predicate: RDF_REST.to_string(), object: head });
+            head = node;
+        }
+        Ok(head)
+    }
+
+    fn new_blank(&mut self) -> Term {
+        self.next_blank += 1;
+        // Labeled blank nodes keep their `_:` prefix, so generated labels can't clash with them.
+        Term::Blank(format!("b{}", self.next_blank))
+    }
+
+    /// Converts an IRI, prefixed name or blank node label token into a term.
+    fn node(&self, token: Token) -> Result<Term, String> {
+        match token {
+            Token::Name(ref name) if name.starts_with("_:") => Ok(Term::Blank(name.clone())),
+            token => self.resource(token).map(Term::Iri)
+        }
+    }
+
+    fn resource(&self, token: Token) -> Result<String, String> {
+        match token {
+            Token::IriRef(iri) => Ok(self.resolve(iri)),
+            Token::Name(name) => {
+                let colon = name.find(':').ok_or_else(|| format!("invalid name '{}'", name))?;
+                let namespace = self.prefixes.get(&name[..colon])
+                    .ok_or_else(|| format!("undefined prefix in '{}'", name))?;
+                Ok(format!("{}{}", namespace, &name[colon + 1..]))
+            },
+            other => Err(format!("expected IRI, found {:?}", other))
+        }
+    }
+
+    /// Resolves a relative IRI reference against the base IRI, if one has been declared. Dot
+    /// segments are not removed.
+    fn resolve(&self, iri: String) -> String {
+        let is_absolute = iri.find(':')
+            .is_some_and(|colon| colon > 0 && !iri[..colon].contains(['/', '?', '#']));
+        let base = match &self.base {
+            Some(base) if !is_absolute => base,
+            _ => return iri
+        };
+        let without_fragment = base.split('#').next().unwrap_or(base);
+        let authority_end = base.find("://")
+            .map(|start| base[start + 3..].find('/').map_or(base.len(), |end| start + 3 + end))
+            .unwrap_or(0);
+        if iri.is_empty() {
+            without_fragment.to_string()
+        }
+        else if iri.starts_with('#') {
+            format!("{}{}", without_fragment, iri)
+        }
+        else if iri.starts_with('/') {
+            format!("{}{}", &base[..authority_end], iri)
+        }
+        else {
+            let directory_end = without_fragment.rfind('/').map_or(0, |slash| slash + 1).max(authority_end);
+            let separator = if directory_end == authority_end && !base[..directory_end].ends_with('/') { "/" } else { "" };
+            format!("{}{}{}", &base[..directory_end], separator, iri)
+        }
+    }
+}
+
+/// Checks if a name token is a boolean or numeric literal.
+fn is_bare_literal(name: &str) -> bool {
+    name == "true" || name == "false"
+        || name.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EX: &str = "http://example.org/ns#";
+
+    fn iri(local_name: &str) -> Term {
+        Term::Iri(format!("{}{}", EX, local_name))
+    }
+
+    fn literal(value: &str) -> Term {
+        Term::Literal(value.to_string())
+    }
+
+    fn triple(subject: Term, predicate: &str, object: Term) -> Triple {
+        Triple { subject, predicate: predicate.to_string(), object }
+    }
+
+    #[test]
+    fn prefixes_and_a_expand_to_iris() {
+        let triples = parse_turtle("@prefix ex: <http://example.org/ns#> .\n\
+            ex:thing a ex:Class .\n\
+            <http://example.org/other> ex:label \"other\" .").unwrap();
+        assert_eq!(triples, vec![
+            triple(iri("thing"), RDF_TYPE, iri("Class")),
+            triple(Term::Iri(String::from("http://example.org/other")), &format!("{}label", EX), literal("other"))
+        ]);
+    }
+
+    #[test]
+    fn predicate_and_object_lists_share_subjects() {
+        let triples = parse_turtle("@prefix ex: <http://example.org/ns#> .\n\
+            ex:thing\n\
+            \ta ex:A ,\n\
+            \t\tex:B ;\n\
+            \tex:size 3 ;\n\
+            \tex:flag true ; .").unwrap();
+        assert_eq!(triples, vec![
+            triple(iri("thing"), RDF_TYPE, iri("A")),
+            triple(iri("thing"), RDF_TYPE, iri("B")),
+            triple(iri("thing"), &format!("{}size", EX), literal("3")),
+            triple(iri("thing"), &format!("{}flag", EX), literal("true"))
+        ]);
+    }
+
+    #[test]
+    fn comments_and_multi_line_strings() {
+        let triples = parse_turtle("# Leading comment.\n\
+            @prefix ex: <http://example.org/ns#> . # Trailing comment.\n\
+            ex:thing ex:comment \"\"\"First line, with a \"quote\".\n\
+            # Not a comment.\"\"\" ;\n\
+            \t# Comment between predicates.\n\
+            \tex:label 'single'@en , \"typed\"^^ex:Type , \"esc\\u00E9\\n\\\"\" .").unwrap();
+        let comment = format!("{}comment", EX);
+        let label = format!("{}label", EX);
+        assert_eq!(triples, vec![
+            triple(iri("thing"), &comment, literal("First line, with a \"quote\".\n# Not a comment.")),
+            triple(iri("thing"), &label, literal("single")),
+            triple(iri("thing"), &label, literal("typed")),
+            triple(iri("thing"), &label, literal("esc\u{e9}\n\""))
+        ]);
+    }
+
+    #[test]
+    fn blank_node_property_lists() {
+        let triples = parse_turtle("@prefix ex: <http://example.org/ns#> .\n\
+            ex:thing ex:part [ ex:size 1 ] .").unwrap();
+        assert_eq!(triples.len(), 2);
+        assert_eq!(triples[0].subject, triples[1].object);
+        assert_eq!(triples[1].subject, iri("thing"));
+    }
+
+    #[test]
+    fn blank_node_labels_are_shared() {
+        let triples = parse_turtle("@prefix ex: <http://example.org/ns#> .\n\
+            _:b1 ex:size 1 .\n\
+            ex:thing ex:part _:b1 , [] .").unwrap();
+        assert_eq!(triples.len(), 3);
+        assert_eq!(triples[0].subject, triples[1].object);
+        assert!(matches!(triples[2].object, Term::Blank(_)));
+        assert_ne!(triples[1].object, triples[2].object);
+    }
+
+    #[test]
+    fn collections_expand_to_lists() {
+        let triples = parse_turtle("@prefix ex: <http://example.org/ns#> .\n\
+            ex:thing ex:parts ( ex:a \"b\" ) ; ex:none () .").unwrap();
+        assert_eq!(triples.len(), 6);
+        let head = triples.iter().find(|triple| triple.predicate == format!("{}parts", EX)).unwrap().object.clone();
+        let object = |subject: &Term, predicate: &str| triples.iter()
+            .find(|triple| &triple.subject == subject && triple.predicate == predicate)
+            .map(|triple| triple.object.clone())
+            .unwrap();
+        assert_eq!(object(&head, RDF_FIRST), iri("a"));
+        let rest = object(&head, RDF_REST);
+        assert_eq!(object(&rest, RDF_FIRST), literal("b"));
+        assert_eq!(object(&rest, RDF_REST), Term::Iri(RDF_NIL.to_string()));
+        assert_eq!(object(&iri("thing"), &format!("{}none", EX)), Term::Iri(RDF_NIL.to_string()));
+    }
+
+    #[test]
+    fn base_and_sparql_directives() {
+        let triples = parse_turtle("@base <http://example.org/dir/file.ttl> .\n\
+            PREFIX ex: <ns#>\n\
+            <#thing> ex:a <other> , </root> , <> .\n\
+            BASE <http://example.org/>\n\
+            <thing> ex:b <http://example.com/absolute> .").unwrap();
+        let predicate = |name: &str| format!("http://example.org/dir/ns#{}", name);
+        let node = |iri: &str| Term::Iri(iri.to_string());
+        assert_eq!(triples, vec![
+            triple(node("http://example.org/dir/file.ttl#thing"), &predicate("a"), node("http://example.org/dir/other")),
+            triple(node("http://example.org/dir/file.ttl#thing"), &predicate("a"), node("http://example.org/root")),
+            triple(node("http://example.org/dir/file.ttl#thing"), &predicate("a"), node("http://example.org/dir/file.ttl")),
+            triple(node("http://example.org/thing"), &predicate("b"), node("http://example.com/absolute"))
+        ]);
+    }
+
+    #[test]
+    fn unsupported_constructs_are_errors() {
+        let prefix = "@prefix ex: <http://example.org/ns#> .\n";
+        for input in &[
+            "@keywords a .",
+            "ex:thing ex:parts ( ex:a ex:b .",
+            "ex:thing ex:formula { ex:a ex:b ex:c } .",
+            "ex:thing ex:label \"bad \\q escape\" .",
+            "ex:thing ex:label \"unterminated\n\" .",
+            "other:thing a ex:Class .",
+            "ex:thing a ex:Class"
+        ] {
+            assert!(parse_turtle(&format!("{}{}", prefix, input)).is_err(), "accepted {:?}", input);
+        }
+    }
+}
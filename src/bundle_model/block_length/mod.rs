@@ -0,0 +1,304 @@
+//! Representation of the block length constraints that a plugin places on its host, as defined by
+//! the LV2 buf-size extension.
+
+use enumset::EnumSet;
+use crate::bundle_model::constants::{HostFeature, Lv2Option};
+use crate::bundle_model::options::{HostOptions, OptionValue};
+use crate::bundle_model::impl_util::HostFeatureRequirer;
+
+/// Error type returned when trying to construct an invalid host block configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidBlockConfigError {
+    /// Description of the problem with the configuration.
+    pub reason: &'static str
+}
+
+/// Represents the way a host will divide audio into blocks when running a plugin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HostBlockConfig {
+    /// Minimum number of samples per block.
+    min_block_length: u32,
+
+    /// Maximum number of samples per block.
+    max_block_length: u32,
+
+    /// Typical number of samples per block, if the host knows it.
+    nominal_block_length: Option<u32>,
+
+    /// Maximum size (in bytes) of atom sequences, if the host knows it.
+    sequence_size: Option<u32>,
+
+    /// Indicates whether every block has the same length.
+    fixed: bool,
+
+    /// Indicates whether every block length is a power of 2.
+    power_of_2: bool
+}
+
+impl HostBlockConfig {
+    /// Constructs a host block configuration.
+    ///
+    /// # Parameters
+    /// - `min_block_length`: Minimum number of samples per block.
+    /// - `max_block_length`: Maximum number of samples per block.
+    /// - `fixed`: Indicates whether every block has the same length. If so, `min_block_length` and
+    ///   `max_block_length` must be equal.
+    /// - `power_of_2`: Indicates whether every block length is a power of 2. If so,
+    ///   `min_block_length` and `max_block_length` must be powers of 2.
+    ///
+    /// # Errors
+    /// Returns an error if the parameters are inconsistent.
+    pub fn new(min_block_length: u32, max_block_length: u32, fixed: bool, power_of_2: bool)
+        -> Result<Self, InvalidBlockConfigError>
+    {
+        if min_block_length > max_block_length {
+            return Err(InvalidBlockConfigError { reason: "minimum block length exceeds maximum" });
+        }
+        if fixed && min_block_length != max_block_length {
+            return Err(InvalidBlockConfigError {
+                reason: "fixed block length requires equal minimum and maximum"
+            });
+        }
+        if power_of_2 && !(min_block_length.is_power_of_two() && max_block_length.is_power_of_two()) {
+            return Err(InvalidBlockConfigError {
+                reason: "power-of-2 block length requires power-of-2 bounds"
+            });
+        }
+
+        Ok(HostBlockConfig {
+            min_block_length,
+            max_block_length,
+            nominal_block_length: None,
+            sequence_size: None,
+            fixed,
+            power_of_2
+        })
+    }
+
+    /// Returns a copy of this configuration with the specified nominal block length.
+    ///
+    /// # Errors
+    /// Returns an error if the nominal block length is outside the configuration's bounds.
+    pub fn with_nominal_block_length(self, nominal_block_length: u32)
+        -> Result<Self, InvalidBlockConfigError>
+    {
+        if nominal_block_length < self.min_block_length || nominal_block_length > self.max_block_length {
+            return Err(InvalidBlockConfigError { reason: "nominal block length is out of bounds" });
+        }
+        Ok(HostBlockConfig { nominal_block_length: Some(nominal_block_length), ..self })
+    }
+
+    /// Returns a copy of this configuration with the specified maximum atom sequence size, in
+    /// bytes.
+    pub fn with_sequence_size(self, sequence_size: u32) -> Self {
+        HostBlockConfig { sequence_size: Some(sequence_size), ..self }
+    }
+
+    /// Gets the minimum number of samples per block.
+    pub fn min_block_length(&self) -> u32 {
+        self.min_block_length
+    }
+
+    /// Gets the maximum number of samples per block.
+    pub fn max_block_length(&self) -> u32 {
+        self.max_block_length
+    }
+
+    /// Gets the typical number of samples per block, if known.
+    pub fn nominal_block_length(&self) -> Option<u32> {
+        self.nominal_block_length
+    }
+
+    /// Gets the maximum atom sequence size in bytes, if known.
+    pub fn sequence_size(&self) -> Option<u32> {
+        self.sequence_size
+    }
+
+    /// Checks if every block has the same length.
+    pub fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    /// Checks if every block length is a power of 2.
+    pub fn is_power_of_2(&self) -> bool {
+        self.power_of_2
+    }
+
+    /// Sets the buf-size option values described by this configuration.
+    ///
+    /// # Parameters
+    /// - `host_options`: The option values to update.
+    pub fn apply_to(&self, host_options: &mut HostOptions) {
+        let mut set_int = |option, value: u32| {
+            // Values beyond i32::MAX cannot be represented in an atom:Int, so they are clamped.
+            let value = OptionValue::Int(value.min(i32::MAX as u32) as i32);
+            host_options.set(option, value).expect("buf-size options have integer values");
+        };
+        set_int(Lv2Option::MinBlockLength, self.min_block_length);
+        set_int(Lv2Option::MaxBlockLength, self.max_block_length);
+        if let Some(nominal_block_length) = self.nominal_block_length {
+            set_int(Lv2Option::NominalBlockLength, nominal_block_length);
+        }
+        if let Some(sequence_size) = self.sequence_size {
+            set_int(Lv2Option::SequenceSize, sequence_size);
+        }
+    }
+}
+
+/// Represents a way in which a host block configuration fails to satisfy a
+/// [`BlockLengthContract`](self::BlockLengthContract).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BlockLengthViolation {
+    /// The plugin requires every block to have the same length, but the host does not guarantee
+    /// this.
+    NotFixed,
+
+    /// The plugin requires every block length to be a power of 2, but the host does not guarantee
+    /// this.
+    NotPowerOf2,
+
+    /// The plugin requires a buf-size option that the host configuration does not provide.
+    MissingOption(Lv2Option)
+}
+
+/// Represents the block length constraints that a plugin places on its host. A host that cannot
+/// honour the contract directly can still run the plugin through an adapter that re-blocks the
+/// audio into a compatible configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlockLengthContract {
+    /// Indicates whether the plugin requires the host to specify block length bounds.
+    bounded: bool,
+
+    /// Indicates whether the plugin requires every block to have the same length.
+    fixed: bool,
+
+    /// Indicates whether the plugin requires every block length to be a power of 2.
+    power_of_2: bool,
+
+    /// Indicates whether the plugin prefers the host to avoid splitting blocks.
+    coarse: bool,
+
+    /// Buf-size options that the host must provide.
+    required_options: EnumSet<Lv2Option>
+}
+
+impl BlockLengthContract {
+    /// Checks if the plugin requires the host to specify upper and lower bounds on the block
+    /// length.
+    pub fn is_bounded(&self) -> bool {
+        self.bounded
+    }
+
+    /// Checks if the plugin requires every block to have the same length.
+    pub fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    /// Checks if the plugin requires every block length to be a power of 2.
+    pub fn is_power_of_2(&self) -> bool {
+        self.power_of_2
+    }
+
+    /// Checks if the plugin prefers the host to avoid splitting blocks, e.g. at event timestamps.
+    /// This is a preference rather than a requirement, so it never causes a violation.
+    pub fn is_coarse(&self) -> bool {
+        self.coarse
+    }
+
+    /// Gets the buf-size options that the host must provide to the plugin.
+    pub fn required_options(&self) -> EnumSet<Lv2Option> {
+        self.required_options
+    }
+
+    /// Checks a host block configuration against this contract, returning every violation found.
+    /// An empty result means the host can run the plugin directly; otherwise the host needs to
+    /// adapt its block configuration (e.g. by re-blocking) before running the plugin.
+    pub fn check(&self, config: &HostBlockConfig) -> Vec<BlockLengthViolation> {
+        let mut violations = Vec::new();
+        if self.fixed && !config.fixed {
+            violations.push(BlockLengthViolation::NotFixed);
+        }
+        if self.power_of_2 && !config.power_of_2 {
+            violations.push(BlockLengthViolation::NotPowerOf2);
+        }
+        for option in self.required_options {
+            let provided = match option {
+                Lv2Option::NominalBlockLength => config.nominal_block_length.is_some(),
+                Lv2Option::SequenceSize => config.sequence_size.is_some(),
+                _ => true
+            };
+            if !provided {
+                violations.push(BlockLengthViolation::MissingOption(option));
+            }
+        }
+        violations
+    }
+
+    /// Checks if a host block configuration satisfies this contract.
+    pub fn is_satisfied_by(&self, config: &HostBlockConfig) -> bool {
+        self.check(config).is_empty()
+    }
+}
+
+impl HostFeatureRequirer {
+    /// Computes the block length contract implied by the required host features (including
+    /// implied features, so that e.g. a fixed block length is also bounded) and options.
+    pub(crate) fn block_length_contract(&self) -> BlockLengthContract {
+        let required_features = self.all_required_host_features();
+        let all_features = required_features.union(self.optional_host_features.knowns());
+        let buf_size_options = Lv2Option::MinBlockLength | Lv2Option::MaxBlockLength
+            | Lv2Option::NominalBlockLength | Lv2Option::SequenceSize;
+        let mut required_options = self.required_options.knowns().intersection(buf_size_options);
+        let bounded = required_features.contains(HostFeature::BoundedBlockLength);
+        if bounded {
+            required_options |= Lv2Option::MinBlockLength | Lv2Option::MaxBlockLength;
+        }
+
+        BlockLengthContract {
+            bounded,
+            fixed: required_features.contains(HostFeature::FixedBlockLength),
+            power_of_2: required_features.contains(HostFeature::PowerOf2BlockLength),
+            coarse: all_features.contains(HostFeature::CoarseBlockLength),
+            required_options
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdf_util::Graph;
+    use crate::bundle_model::constants::IriConstant;
+    use crate::bundle_model::loader::{load_plugin, LV2_REQUIRED_FEATURE, OPTS_REQUIRED_OPTION};
+    use crate::bundle_model::test_util::{iri, iri_node, add_plugin};
+
+    #[test]
+    fn host_config_is_checked_against_contract() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        graph.insert(plugin.clone(), iri(LV2_REQUIRED_FEATURE), iri_node(HostFeature::FixedBlockLength.iri()));
+        graph.insert(plugin.clone(), iri(LV2_REQUIRED_FEATURE), iri_node(HostFeature::PowerOf2BlockLength.iri()));
+        graph.insert(plugin, iri(OPTS_REQUIRED_OPTION), iri_node(Lv2Option::NominalBlockLength.iri()));
+        let contract = load_plugin(&graph, &iri("urn:test:plugin")).unwrap().block_length_contract();
+        assert!(contract.is_fixed() && contract.is_power_of_2() && contract.is_bounded());
+
+        let variable = HostBlockConfig::new(1, 4096, false, false).unwrap();
+        assert_eq!(contract.check(&variable), vec![
+            BlockLengthViolation::NotFixed,
+            BlockLengthViolation::NotPowerOf2,
+            BlockLengthViolation::MissingOption(Lv2Option::NominalBlockLength)
+        ]);
+
+        let fixed = HostBlockConfig::new(512, 512, true, true).unwrap()
+            .with_nominal_block_length(512).unwrap();
+        assert!(contract.is_satisfied_by(&fixed));
+    }
+
+    #[test]
+    fn inconsistent_host_configs_are_rejected() {
+        assert!(HostBlockConfig::new(64, 32, false, false).is_err());
+        assert!(HostBlockConfig::new(32, 64, true, false).is_err());
+        assert!(HostBlockConfig::new(48, 48, true, true).is_err());
+        assert!(HostBlockConfig::new(32, 64, false, false).unwrap().with_nominal_block_length(128).is_err());
+    }
+}
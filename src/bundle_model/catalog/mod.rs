@@ -0,0 +1,250 @@
+//! Representation of the set of plugins installed across multiple LV2 bundles.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::cmp::Ordering;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use crate::rdf_util::{Graph, Iri};
+use crate::enum_graph::dot_string;
+use crate::bundle_model::IdentifiedBy;
+use crate::bundle_model::plugin::PluginInfo;
+use crate::bundle_model::hierarchy::PluginTypeHierarchy;
+use crate::bundle_model::loader::{load_plugins, LoadError};
+
+/// Representation of a plugin description, along with the bundle from which it was loaded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InstalledPlugin {
+    /// The plugin description.
+    info: PluginInfo,
+
+    /// Path to the bundle containing the plugin description.
+    bundle_path: PathBuf
+}
+
+impl InstalledPlugin {
+    /// Constructs an installed plugin object.
+    ///
+    /// # Parameters
+    /// - `info`: The plugin description.
+    /// - `bundle_path`: Path to the bundle containing the plugin description.
+    pub fn new(info: PluginInfo, bundle_path: PathBuf) -> Self {
+        InstalledPlugin {
+            info,
+            bundle_path
+        }
+    }
+
+    /// Gets the plugin description.
+    pub fn info(&self) -> &PluginInfo {
+        &self.info
+    }
+
+    /// Gets the path to the bundle containing the plugin description.
+    pub fn bundle_path(&self) -> &Path {
+        &self.bundle_path
+    }
+}
+
+/// Representation of the plugins installed on a system. The same plugin (i.e. the same plugin
+/// IRI) may be installed in multiple bundles, typically with different versions. In that case, the
+/// catalog selects the version that a host should use, according to
+/// [`ResourceVersion::cmp_preference`](crate::bundle_model::ResourceVersion::cmp_preference). The
+/// other versions are considered "shadowed", but remain available so that users can see why they
+/// are being ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PluginCatalog {
+    /// All installed copies of each plugin, keyed by plugin IRI. Each list is nonempty and ordered
+    /// from most preferred to least preferred.
+    plugins: BTreeMap<Iri, Vec<InstalledPlugin>>
+}
+
+impl PluginCatalog {
+    /// Constructs an empty catalog.
+    pub fn new() -> Self {
+        PluginCatalog {
+            plugins: BTreeMap::new()
+        }
+    }
+
+    /// Adds a plugin description to the catalog. If another description of the same plugin is
+    /// already present with an equally preferred version, the existing description stays
+    /// selected.
+    ///
+    /// # Parameters
+    /// - `info`: The plugin description.
+    /// - `bundle_path`: Path to the bundle containing the plugin description.
+    pub fn insert(&mut self, info: PluginInfo, bundle_path: PathBuf) {
+        let copies = self.plugins.entry(info.id().clone()).or_default();
+        let position = copies.iter()
+            .position(|copy| info.version().cmp_preference(copy.info.version()) == Ordering::Greater)
+            .unwrap_or(copies.len());
+        copies.insert(position, InstalledPlugin::new(info, bundle_path));
+    }
+
+    /// Loads all plugins described in a bundle and adds them to the catalog.
+    ///
+    /// # Parameters
+    /// - `graph`: Graph containing the bundle's RDF data.
+    /// - `bundle_path`: Path to the bundle.
+    ///
+    /// # Errors
+    /// Returns an error if any plugin description in the bundle cannot be loaded. In that case, no
+    /// plugins from the bundle are added.
+    pub fn add_bundle(&mut self, graph: &Graph, bundle_path: &Path) -> Result<(), LoadError> {
+        for plugin in load_plugins(graph)? {
+            self.insert(plugin, bundle_path.to_path_buf());
+        }
+        Ok(())
+    }
+
+    /// Gets the selected copy of the specified plugin, or [`None`](std::option::Option::None) if
+    /// the plugin is not in the catalog.
+    pub fn plugin(&self, iri: &Iri) -> Option<&InstalledPlugin> {
+        self.plugins.get(iri).and_then(|copies| copies.first())
+    }
+
+    /// Gets the copies of the specified plugin that are shadowed by the selected copy, ordered
+    /// from most preferred to least preferred. Returns an empty slice if the plugin is not in the
+    /// catalog or is only installed once.
+    pub fn shadowed(&self, iri: &Iri) -> &[InstalledPlugin] {
+        self.plugins.get(iri).map_or(&[], |copies| &copies[1..])
+    }
+
+    /// Gets a sequential iterator over the selected copy of each plugin in the catalog.
+    pub fn plugins_seq_iter(&self) -> impl Iterator<Item = &InstalledPlugin> {
+        self.plugins.values().map(|copies| &copies[0])
+    }
+
+    /// Gets a (parallel) iterator over the selected copy of each plugin in the catalog.
+    #[cfg(feature = "rayon")]
+    pub fn plugins_iter(&self) -> impl ParallelIterator<Item = &InstalledPlugin> {
+        self.plugins.par_iter().map(|(_, copies)| &copies[0])
+    }
+
+    /// Formats the plugin types used by the plugins in the catalog in the Graphviz DOT language,
+    /// for drawing how the installed plugins are categorized. Each plugin type (known or unknown)
+    /// that at least one plugin belongs to, directly or through the hierarchy, is drawn as a node
+    /// labeled with the last segment of its IRI and the number of such plugins. Edges point from
+    /// each type to its supertypes. `lv2:Plugin` itself is omitted.
+    ///
+    /// # Parameters
+    /// - `hierarchy`: The plugin type hierarchy to use.
+    /// - `transitive_reduction`: If true, only edges to direct supertypes are drawn, i.e. an edge
+    ///   is omitted if the supertype is also reachable through another drawn type.
+    pub fn plugin_types_dot(&self, hierarchy: &PluginTypeHierarchy, transitive_reduction: bool) -> String {
+        let mut counts: BTreeMap<Iri, usize> = BTreeMap::new();
+        for plugin in self.plugins_seq_iter() {
+            let mut all_types = BTreeSet::new();
+            for plugin_type in plugin.info().stated_plugin_type_iris() {
+                all_types.extend(hierarchy.supertypes(&plugin_type));
+                all_types.insert(plugin_type);
+            }
+            for plugin_type in all_types {
+                *counts.entry(plugin_type).or_default() += 1;
+            }
+        }
+
+        let supertypes: BTreeMap<&Iri, BTreeSet<Iri>> = counts.keys()
+            .map(|plugin_type| (plugin_type, hierarchy.supertypes(plugin_type)))
+            .collect();
+
+        let mut output = String::from("digraph {\n    rankdir=BT;\n");
+        for (plugin_type, count) in &counts {
+            let short_name = plugin_type.as_str().rsplit(['#', '/']).next().unwrap_or("");
+            output.push_str(&format!("    {} [label={}];\n", dot_string(plugin_type.as_str()),
+                dot_string(&format!("{} ({})", short_name, count))));
+        }
+        for (plugin_type, all_supertypes) in &supertypes {
+            for supertype in all_supertypes {
+                let redundant = transitive_reduction && all_supertypes.iter()
+                    .any(|other| other != supertype
+                        && supertypes.get(other).is_some_and(|other_supertypes| other_supertypes.contains(supertype)));
+                if !redundant {
+                    output.push_str(&format!("    {} -> {};\n", dot_string(plugin_type.as_str()),
+                        dot_string(supertype.as_str())));
+                }
+            }
+        }
+        output.push_str("}\n");
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle_model::constants::{IriConstant, PluginType};
+    use crate::bundle_model::loader::{RDF_TYPE, LV2_MINOR_VERSION, LV2_MICRO_VERSION};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node, add_plugin};
+
+    fn plugin_graph(minor: u32, micro: u32) -> Graph {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        graph.insert(plugin.clone(), iri(LV2_MINOR_VERSION), literal_node(&minor.to_string()));
+        graph.insert(plugin, iri(LV2_MICRO_VERSION), literal_node(&micro.to_string()));
+        graph
+    }
+
+    fn catalog_with_versions(versions: &[(u32, u32)]) -> PluginCatalog {
+        let mut catalog = PluginCatalog::new();
+        for (bundle_id, (minor, micro)) in versions.iter().enumerate() {
+            let bundle_path = PathBuf::from(format!("bundle{}.lv2", bundle_id));
+            catalog.add_bundle(&plugin_graph(*minor, *micro), &bundle_path).unwrap();
+        }
+        catalog
+    }
+
+    #[test]
+    fn highest_stable_version_is_selected() {
+        let catalog = catalog_with_versions(&[(2, 0), (4, 2), (2, 4)]);
+        let selected = catalog.plugin(&iri("urn:test:plugin")).unwrap();
+        assert_eq!(selected.bundle_path(), Path::new("bundle1.lv2"));
+
+        let shadowed: Vec<_> = catalog.shadowed(&iri("urn:test:plugin")).iter()
+            .map(InstalledPlugin::bundle_path).collect();
+        assert_eq!(shadowed, vec![Path::new("bundle2.lv2"), Path::new("bundle0.lv2")]);
+    }
+
+    #[test]
+    fn stable_release_preferred_over_newer_dev_and_pre_release() {
+        let catalog = catalog_with_versions(&[(0, 8), (3, 0), (2, 2)]);
+        let selected = catalog.plugin(&iri("urn:test:plugin")).unwrap();
+        assert_eq!(selected.bundle_path(), Path::new("bundle2.lv2"));
+        assert_eq!(catalog.shadowed(&iri("urn:test:plugin")).len(), 2);
+    }
+
+    #[test]
+    fn first_copy_wins_on_equal_versions() {
+        let catalog = catalog_with_versions(&[(2, 0), (2, 0)]);
+        let selected = catalog.plugin(&iri("urn:test:plugin")).unwrap();
+        assert_eq!(selected.bundle_path(), Path::new("bundle0.lv2"));
+    }
+
+    #[test]
+    fn catalog_plugin_types_as_dot() {
+        let mut graph = Graph::new();
+        let compressor = add_plugin(&mut graph, "urn:test:compressor");
+        graph.insert(compressor, iri(RDF_TYPE), iri_node(PluginType::Compressor.iri()));
+        let limiter = add_plugin(&mut graph, "urn:test:limiter");
+        graph.insert(limiter.clone(), iri(RDF_TYPE), iri_node(PluginType::Limiter.iri()));
+        graph.insert(limiter, iri(RDF_TYPE), iri_node(PluginType::Dynamics.iri()));
+        let equalizer = add_plugin(&mut graph, "urn:test:equalizer");
+        graph.insert(equalizer, iri(RDF_TYPE), iri_node(PluginType::ParaEQ.iri()));
+        let mut catalog = PluginCatalog::new();
+        catalog.add_bundle(&graph, std::path::Path::new("/test.lv2")).unwrap();
+        let hierarchy = PluginTypeHierarchy::new();
+
+        let dot = catalog.plugin_types_dot(&hierarchy, true);
+        let node = |plugin_type: PluginType, label: &str| format!("\"{}\" [label=\"{}\"];", plugin_type.iri(), label);
+        let edge = |from: PluginType, to: PluginType| format!("\"{}\" -> \"{}\";", from.iri(), to.iri());
+        assert!(dot.contains(&node(PluginType::Dynamics, "DynamicsPlugin (2)")));
+        assert!(dot.contains(&node(PluginType::Limiter, "LimiterPlugin (1)")));
+        assert!(dot.contains(&edge(PluginType::Compressor, PluginType::Dynamics)));
+        assert!(dot.contains(&edge(PluginType::EQ, PluginType::Filter)));
+        assert!(!dot.contains(&edge(PluginType::ParaEQ, PluginType::Filter)));
+        assert!(!dot.contains(PluginType::Reverb.iri()));
+        assert_eq!(dot.matches("->").count(), 4);
+        assert_eq!(catalog.plugin_types_dot(&hierarchy, false).matches("->").count(), 5);
+    }
+}
@@ -0,0 +1,402 @@
+//! Numeric views of control port ranges, taking LV2 port properties into account.
+
+use enumset::EnumSet;
+use num_traits::ToPrimitive;
+use ordered_float::OrderedFloat;
+use crate::bundle_model::constants::PortProperty;
+use crate::bundle_model::port::PortInfo;
+
+/// Represents the numeric range of a control (or CV) port, along with the port properties that
+/// affect how values in the range should be interpreted.
+///
+/// If [`is_relative_to_sample_rate`](self::ControlRange::is_relative_to_sample_rate) returns true,
+/// the bounds and default are multiples of the sample rate, and
+/// [`resolve`](self::ControlRange::resolve) should be used to obtain actual values.
+///
+/// Values can be mapped to and from the normalized range 0 to 1 (e.g. for automation lanes or
+/// MIDI controllers) using [`normalize`](self::ControlRange::normalize) and
+/// [`denormalize`](self::ControlRange::denormalize).
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct ControlRange {
+    /// Soft minimum value, if known.
+    min: Option<f32>,
+
+    /// Default value, if known.
+    default: Option<f32>,
+
+    /// Soft maximum value, if known.
+    max: Option<f32>,
+
+    /// Port properties that affect the interpretation of values. Only contains properties from
+    /// [`range_properties`](self::range_properties).
+    properties: EnumSet<PortProperty>,
+
+    /// Number of evenly spaced points into which the range should be divided for step-wise
+    /// control, if specified.
+    range_steps: Option<u32>,
+
+    /// Values of the port's scale points, sorted in ascending order without duplicates.
+    scale_points: Vec<OrderedFloat<f32>>
+}
+
+/// Gets the set of port properties that affect the interpretation of a control range.
+fn range_properties() -> EnumSet<PortProperty> {
+    PortProperty::IntOnly | PortProperty::Toggle | PortProperty::Enumeration
+        | PortProperty::Logarithmic | PortProperty::BoundsRelativeToSampleRate
+}
+
+impl ControlRange {
+    /// Constructs a control range view of a port. Implied port properties are taken into account,
+    /// so e.g. a trigger port is treated as a toggle. Bounds and default values that are not numeric
+    /// are treated as missing. For a toggle port, missing bounds are assumed to be 0 and 1.
+    pub fn of_port(port: &PortInfo) -> Self {
+        let properties = port.all_port_properties().intersection(range_properties());
+        let toggle = properties.contains(PortProperty::Toggle);
+        let min = port.min_value.as_ref().and_then(|literal| literal.to_f32())
+            .or(if toggle { Some(0.0) } else { None });
+        let max = port.max_value.as_ref().and_then(|literal| literal.to_f32())
+            .or(if toggle { Some(1.0) } else { None });
+        let mut scale_points: Vec<_> = port.scale_pts.iter().map(|point| point.value).collect();
+        scale_points.sort();
+        scale_points.dedup();
+
+        ControlRange {
+            min,
+            default: port.default_value.as_ref().and_then(|literal| literal.to_f32()),
+            max,
+            properties,
+            range_steps: port.range_steps.as_ref().and_then(ToPrimitive::to_u32),
+            scale_points
+        }
+    }
+
+    /// Returns a copy of this range with additional port properties, and with the specified
+    /// bounds and default value filling in any that are missing. Values already present are kept.
+    /// The fallback bounds are only used if neither bound is present, since mixing a stated bound
+    /// with an implied one could produce a range that matches neither.
+    pub(crate) fn with_fallbacks(&self, properties: EnumSet<PortProperty>, bounds: Option<(f32, f32)>,
+        default: Option<f32>) -> ControlRange
+    {
+        let properties = self.properties | properties.intersection(range_properties());
+        let toggle = properties.contains(PortProperty::Toggle);
+        let bounds = bounds.filter(|_| self.min.is_none() && self.max.is_none());
+        ControlRange {
+            min: self.min.or(bounds.map(|(min, _)| min)).or(if toggle { Some(0.0) } else { None }),
+            default: self.default.or(default),
+            max: self.max.or(bounds.map(|(_, max)| max)).or(if toggle { Some(1.0) } else { None }),
+            properties,
+            range_steps: self.range_steps,
+            scale_points: self.scale_points.clone()
+        }
+    }
+
+    /// Gets the soft minimum value, or [`None`](std::option::Option::None) if not known.
+    pub fn min(&self) -> Option<f32> {
+        self.min
+    }
+
+    /// Gets the default value, or [`None`](std::option::Option::None) if not known.
+    pub fn default(&self) -> Option<f32> {
+        self.default
+    }
+
+    /// Gets the soft maximum value, or [`None`](std::option::Option::None) if not known.
+    pub fn max(&self) -> Option<f32> {
+        self.max
+    }
+
+    /// Checks if the port's only useful values are integers.
+    pub fn is_int_only(&self) -> bool {
+        self.properties.contains(PortProperty::IntOnly)
+    }
+
+    /// Checks if the port's value represents a boolean.
+    pub fn is_toggle(&self) -> bool {
+        self.properties.contains(PortProperty::Toggle)
+    }
+
+    /// Checks if the port's only useful values are those defined by its scale points.
+    pub fn is_enumeration(&self) -> bool {
+        self.properties.contains(PortProperty::Enumeration)
+    }
+
+    /// Checks if the port's value is on a logarithmic scale.
+    pub fn is_logarithmic(&self) -> bool {
+        self.properties.contains(PortProperty::Logarithmic)
+    }
+
+    /// Checks if the bounds and default value are multiples of the sample rate.
+    pub fn is_relative_to_sample_rate(&self) -> bool {
+        self.properties.contains(PortProperty::BoundsRelativeToSampleRate)
+    }
+
+    /// Gets the number of evenly spaced points into which the range should be divided for
+    /// step-wise control (e.g. arrow keys or a rotary encoder), or
+    /// [`None`](std::option::Option::None) if not specified.
+    pub fn range_steps(&self) -> Option<u32> {
+        self.range_steps
+    }
+
+    /// Resolves a range whose bounds are relative to the sample rate into absolute values, by
+    /// multiplying the bounds and default value by the sample rate. Ranges that are not relative
+    /// to the sample rate are returned unchanged. The returned range is never relative to the
+    /// sample rate.
+    ///
+    /// # Parameters
+    /// - `sample_rate`: The sample rate in Hertz.
+    pub fn resolve(&self, sample_rate: f32) -> ControlRange {
+        if !self.is_relative_to_sample_rate() {
+            return self.clone();
+        }
+
+        let scale = |value: Option<f32>| value.map(|value| value * sample_rate);
+        ControlRange {
+            min: scale(self.min),
+            default: scale(self.default),
+            max: scale(self.max),
+            properties: self.properties - PortProperty::BoundsRelativeToSampleRate,
+            range_steps: self.range_steps,
+            scale_points: self.scale_points.clone()
+        }
+    }
+
+    /// Maps a port value to the range 0 to 1. The value is first snapped to a value the port can
+    /// usefully take (see [`snap`](self::ControlRange::snap)), then mapped linearly, or
+    /// logarithmically for a logarithmic port. If the range has
+    /// [`range_steps`](self::ControlRange::range_steps), the result is snapped to the nearest step.
+    /// For an enumeration port with scale points, the value is instead mapped by the position of
+    /// its scale point in ascending order, so that the scale points are evenly spaced between 0
+    /// and 1 regardless of their values.
+    ///
+    /// Returns [`None`](std::option::Option::None) if the range has no usable bounds (both bounds
+    /// must be known, and the minimum must be less than the maximum), or if the bounds are
+    /// relative to the sample rate and have not been [`resolve`](self::ControlRange::resolve)d.
+    pub fn normalize(&self, value: f32) -> Option<f32> {
+        let (min, max) = self.bounds()?;
+        let value = self.snap(value)?;
+        if let Some(points) = self.enumeration_points() {
+            let index = points.binary_search(&OrderedFloat(value)).unwrap_or(0);
+            return Some(if points.len() > 1 { index as f32 / (points.len() - 1) as f32 } else { 0.0 });
+        }
+        let position = if self.uses_log_scale(min, max) {
+            (value / min).ln() / (max / min).ln()
+        }
+        else {
+            (value - min) / (max - min)
+        };
+        Some(self.snap_to_step(position.clamp(0.0, 1.0)))
+    }
+
+    /// Maps a value in the range 0 to 1 to a port value. This is the inverse of
+    /// [`normalize`](self::ControlRange::normalize): inputs outside 0 to 1 are clamped, snapped to
+    /// the nearest of the [`range_steps`](self::ControlRange::range_steps) if specified, mapped
+    /// linearly or logarithmically, and finally [`snap`](self::ControlRange::snap)ped. For a toggle
+    /// port, positions below 0.5 map to the minimum (false) and all others to the maximum (true).
+    /// For an enumeration port with scale points, the position is rounded to the nearest of the
+    /// evenly spaced scale point positions.
+    ///
+    /// Returns [`None`](std::option::Option::None) under the same conditions as
+    /// [`normalize`](self::ControlRange::normalize).
+    pub fn denormalize(&self, position: f32) -> Option<f32> {
+        let (min, max) = self.bounds()?;
+        if let Some(points) = self.enumeration_points() {
+            let index = (position.clamp(0.0, 1.0) * (points.len() - 1) as f32).round() as usize;
+            return Some(points[index].into_inner());
+        }
+        let position = self.snap_to_step(position.clamp(0.0, 1.0));
+        if self.is_toggle() {
+            return Some(if position < 0.5 { min } else { max });
+        }
+        let value = if self.uses_log_scale(min, max) {
+            min * (max / min).powf(position)
+        }
+        else {
+            min + position * (max - min)
+        };
+        self.snap(value)
+    }
+
+    /// Snaps a value to the nearest value that the port can usefully take, after clamping it to
+    /// the bounds:
+    /// - For a toggle port, any value above the minimum becomes the maximum (i.e. true), and any
+    ///   other value becomes the minimum (i.e. false).
+    /// - For an enumeration port with scale points, the value becomes the nearest scale point.
+    /// - For an integer port, the value is rounded to the nearest integer within the bounds.
+    ///
+    /// Returns [`None`](std::option::Option::None) under the same conditions as
+    /// [`normalize`](self::ControlRange::normalize).
+    pub fn snap(&self, value: f32) -> Option<f32> {
+        let (min, max) = self.bounds()?;
+        let value = value.clamp(min, max);
+        if self.is_toggle() {
+            return Some(if value > min { max } else { min });
+        }
+        if let Some(points) = self.enumeration_points() {
+            return points.iter()
+                .map(|point| point.into_inner())
+                .min_by_key(|point| OrderedFloat((point - value).abs()));
+        }
+        if self.is_int_only() {
+            let rounded = value.round();
+            return Some(if rounded < min { min.ceil() } else if rounded > max { max.floor() } else { rounded });
+        }
+        Some(value)
+    }
+
+    /// Gets the bounds of the range, if they are known, absolute and non-empty.
+    fn bounds(&self) -> Option<(f32, f32)> {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) if min < max && !self.is_relative_to_sample_rate() => Some((min, max)),
+            _ => None
+        }
+    }
+
+    /// Gets the sorted scale points of an enumeration port, or [`None`](std::option::Option::None)
+    /// if the port is not an enumeration or has no scale points.
+    fn enumeration_points(&self) -> Option<&[OrderedFloat<f32>]> {
+        if self.is_enumeration() && !self.scale_points.is_empty() {
+            Some(&self.scale_points)
+        }
+        else {
+            None
+        }
+    }
+
+    /// Checks if values should be mapped logarithmically. A logarithmic scale is only possible if
+    /// both bounds have the same sign and are nonzero; otherwise the mapping falls back to linear.
+    fn uses_log_scale(&self, min: f32, max: f32) -> bool {
+        self.is_logarithmic() && min * max > 0.0
+    }
+
+    /// Snaps a normalized position to the nearest of the range steps, if specified.
+    fn snap_to_step(&self, position: f32) -> f32 {
+        match self.range_steps {
+            Some(steps) if steps >= 2 => {
+                let intervals = (steps - 1) as f32;
+                (position * intervals).round() / intervals
+            },
+            _ => position
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdf_util::{Graph, Node};
+    use crate::bundle_model::constants::{IriConstant, PortType};
+    use crate::bundle_model::loader::{load_plugin, LV2_MINIMUM, LV2_MAXIMUM, LV2_DEFAULT, LV2_PORT_PROPERTY, PPROPS_RANGE_STEPS, LV2_SCALE_POINT, RDF_VALUE};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node, add_plugin, add_port};
+
+    #[test]
+    fn sample_rate_relative_bounds_are_resolved() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let port = add_port(&mut graph, &plugin, 0, "cutoff",
+            &[PortType::InputPort.iri(), PortType::Control.iri()]);
+        graph.insert(port.clone(), iri(LV2_MINIMUM), literal_node("0.0"));
+        graph.insert(port.clone(), iri(LV2_MAXIMUM), literal_node("0.5"));
+        graph.insert(port.clone(), iri(LV2_DEFAULT), literal_node("0.25"));
+        graph.insert(port.clone(), iri(PPROPS_RANGE_STEPS), literal_node("300"));
+        graph.insert(port, iri(LV2_PORT_PROPERTY),
+            iri_node(PortProperty::BoundsRelativeToSampleRate.iri()));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        let range = plugin.ports()[0].control_range();
+        assert!(range.is_relative_to_sample_rate());
+        assert_eq!(range.range_steps(), Some(300));
+        let resolved = range.resolve(48000.0);
+        assert!(!resolved.is_relative_to_sample_rate());
+        assert_eq!((resolved.min(), resolved.default(), resolved.max()),
+            (Some(0.0), Some(12000.0), Some(24000.0)));
+        assert_eq!(resolved.resolve(44100.0), resolved);
+    }
+
+    #[test]
+    fn toggle_bounds_default_to_zero_and_one() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let port = add_port(&mut graph, &plugin, 0, "enable",
+            &[PortType::InputPort.iri(), PortType::Control.iri()]);
+        graph.insert(port, iri(LV2_PORT_PROPERTY), iri_node(PortProperty::Toggle.iri()));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        let range = plugin.ports()[0].control_range();
+        assert!(range.is_toggle());
+        assert_eq!((range.min(), range.default(), range.max()), (Some(0.0), None, Some(1.0)));
+    }
+
+    fn control_port_range(min: &str, max: &str, properties: &[PortProperty]) -> (Graph, Node, ControlRange) {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let port = add_port(&mut graph, &plugin, 0, "param",
+            &[PortType::InputPort.iri(), PortType::Control.iri()]);
+        graph.insert(port.clone(), iri(LV2_MINIMUM), literal_node(min));
+        graph.insert(port.clone(), iri(LV2_MAXIMUM), literal_node(max));
+        for property in properties {
+            graph.insert(port.clone(), iri(LV2_PORT_PROPERTY), iri_node(property.iri()));
+        }
+        let range = load_plugin(&graph, &iri("urn:test:plugin")).unwrap().ports()[0].control_range();
+        (graph, port, range)
+    }
+
+    #[test]
+    fn logarithmic_ranges_are_normalized_geometrically() {
+        let (_, _, range) = control_port_range("20.0", "20000.0", &[PortProperty::Logarithmic]);
+        assert!((range.normalize(632.455_5).unwrap() - 0.5).abs() < 1e-4);
+        assert!((range.denormalize(0.5).unwrap() - 632.455_5).abs() < 1e-2);
+        assert_eq!(range.normalize(1.0), Some(0.0));
+
+        let (_, _, linear) = control_port_range("-1.0", "1.0", &[PortProperty::Logarithmic]);
+        assert_eq!(linear.normalize(0.0), Some(0.5));
+    }
+
+    #[test]
+    fn values_are_snapped_to_useful_values() {
+        let (_, _, range) = control_port_range("0.0", "10.0", &[PortProperty::IntOnly]);
+        assert_eq!(range.denormalize(0.33), Some(3.0));
+
+        let (_, _, range) = control_port_range("0.0", "1.0", &[PortProperty::Toggle]);
+        assert_eq!(range.normalize(0.2), Some(1.0));
+        assert_eq!(range.denormalize(0.4), Some(0.0));
+
+        let (mut graph, port, _) = control_port_range("0.0", "8.0", &[PortProperty::Enumeration]);
+        for value in &["1.0", "2.0", "7.0"] {
+            let point = Node::BlankNode(format!("point{}", value));
+            graph.insert(port.clone(), iri(LV2_SCALE_POINT), point.clone());
+            graph.insert(point, iri(RDF_VALUE), literal_node(value));
+        }
+        let range = load_plugin(&graph, &iri("urn:test:plugin")).unwrap().ports()[0].control_range();
+        assert_eq!(range.snap(5.0), Some(7.0));
+        assert_eq!(range.denormalize(0.0), Some(1.0));
+
+        let (mut graph, port, _) = control_port_range("0.0", "1.0", &[]);
+        graph.insert(port, iri(PPROPS_RANGE_STEPS), literal_node("5"));
+        let range = load_plugin(&graph, &iri("urn:test:plugin")).unwrap().ports()[0].control_range();
+        assert_eq!(range.normalize(0.3), Some(0.25));
+        assert_eq!(range.denormalize(0.9), Some(1.0));
+    }
+
+    #[test]
+    fn enumerations_are_normalized_by_scale_point_index() {
+        let (mut graph, port, _) = control_port_range("0.0", "100.0",
+            &[PortProperty::Enumeration, PortProperty::Logarithmic]);
+        graph.insert(port.clone(), iri(PPROPS_RANGE_STEPS), literal_node("3"));
+        for value in &["100.0", "1.0", "2.0", "10.0"] {
+            let point = Node::BlankNode(format!("point{}", value));
+            graph.insert(port.clone(), iri(LV2_SCALE_POINT), point.clone());
+            graph.insert(point, iri(RDF_VALUE), literal_node(value));
+        }
+        let range = load_plugin(&graph, &iri("urn:test:plugin")).unwrap().ports()[0].control_range();
+
+        let positions: Vec<_> = [1.0, 2.0, 10.0, 100.0].iter()
+            .map(|value| range.normalize(*value).unwrap())
+            .collect();
+        assert_eq!(positions, vec![0.0, 1.0 / 3.0, 2.0 / 3.0, 1.0]);
+        assert_eq!(range.normalize(9.0), Some(2.0 / 3.0));
+        let values: Vec<_> = [0.0, 0.2, 0.4, 0.6, 0.9, 1.5].iter()
+            .map(|position| range.denormalize(*position).unwrap())
+            .collect();
+        assert_eq!(values, vec![1.0, 2.0, 2.0, 10.0, 100.0, 100.0]);
+    }
+}
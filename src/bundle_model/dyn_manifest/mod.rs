@@ -7,7 +7,11 @@ use enumset::EnumSetIter;
 use crate::bundle_model::constants::{HostFeature, Lv2Option};
 use crate::bundle_model::unknowns::{UnknownHostFeature, UnknownOption};
 use crate::bundle_model::impl_util::HostFeatureRequirer;
+use crate::bundle_model::options::{HostOptions, OptionValue, MissingOptionsError};
+#[cfg(feature = "rayon")]
 use rayon::iter::{IterBridge, IntoParallelRefIterator};
+use std::collections::btree_set;
+#[cfg(feature = "rayon")]
 use std::collections::BTreeSet;
 
 /// Representation of an LV2 dynamic manifest generator.
@@ -26,6 +30,20 @@ pub struct DynManifestInfo {
     host_feature_requirer: HostFeatureRequirer
 }
 
+impl DynManifestInfo {
+    /// Selects the LV2 option values to pass to the dynamic manifest generator, given the options
+    /// available from the host. Options are selected the same way as for
+    /// [`PluginInfo::select_options`](crate::bundle_model::plugin::PluginInfo::select_options).
+    ///
+    /// # Errors
+    /// Returns an error listing the required options that the host cannot provide, if any.
+    pub fn select_options(&self, host_options: &HostOptions)
+        -> Result<Vec<(Lv2Option, OptionValue)>, MissingOptionsError>
+    {
+        self.host_feature_requirer.select_options(host_options)
+    }
+}
+
 impl OptionallyIdentifiedBy<Iri> for DynManifestInfo {
     fn id(&self) -> Option<&Iri> {
         self.iri.as_ref()
@@ -46,8 +64,15 @@ impl Loadable for DynManifestInfo {
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, HostFeature> for DynManifestInfo {
     type BorrowedElt = HostFeature;
+    type SeqSetIter = EnumSetIter<HostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<HostFeature>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_host_features.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_host_features.knowns_iter()
     }
@@ -55,8 +80,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, HostFeature> for DynManifestInfo {
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownHostFeature> for DynManifestInfo {
     type BorrowedElt = &'a UnknownHostFeature;
+    type SeqSetIter = btree_set::Iter<'a, UnknownHostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownHostFeature> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_host_features.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_host_features.unknowns_iter()
     }
@@ -64,8 +96,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownHostFeature> for DynManifest
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, Lv2Option> for DynManifestInfo {
     type BorrowedElt = Lv2Option;
+    type SeqSetIter = EnumSetIter<Lv2Option>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<Lv2Option>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_options.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_options.knowns_iter()
     }
@@ -73,8 +112,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, Lv2Option> for DynManifestInfo {
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownOption> for DynManifestInfo {
     type BorrowedElt = &'a UnknownOption;
+    type SeqSetIter = btree_set::Iter<'a, UnknownOption>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownOption> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_options.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_options.unknowns_iter()
     }
@@ -82,8 +128,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownOption> for DynManifestInfo
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, HostFeature> for DynManifestInfo {
     type BorrowedElt = HostFeature;
+    type SeqSetIter = EnumSetIter<HostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<HostFeature>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_host_features.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_host_features.knowns_iter()
     }
@@ -91,8 +144,15 @@ impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, HostFeature> for DynManif
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, UnknownHostFeature> for DynManifestInfo {
     type BorrowedElt = &'a UnknownHostFeature;
+    type SeqSetIter = btree_set::Iter<'a, UnknownHostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownHostFeature> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_host_features.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_host_features.unknowns_iter()
     }
@@ -100,8 +160,15 @@ impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, UnknownHostFeature> for D
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, Lv2Option> for DynManifestInfo {
     type BorrowedElt = Lv2Option;
+    type SeqSetIter = EnumSetIter<Lv2Option>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<Lv2Option>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_options.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_options.knowns_iter()
     }
@@ -109,8 +176,15 @@ impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, Lv2Option> for DynManifes
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, UnknownOption> for DynManifestInfo {
     type BorrowedElt = &'a UnknownOption;
+    type SeqSetIter = btree_set::Iter<'a, UnknownOption>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownOption> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_options.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_options.unknowns_iter()
     }
@@ -0,0 +1,147 @@
+//! Views of bundle model objects that merge the data stated in a bundle with the data implied by
+//! LV2 semantics.
+
+use enumset::EnumSet;
+use crate::bundle_model::constants::{PortProperty, Unit};
+use crate::bundle_model::port::PortInfo;
+use crate::bundle_model::control::ControlRange;
+use crate::bundle_model::implications::{PORT_PROPERTIES_IMPLIED, PORT_PROPERTIES_CONFLICTING, port_properties_implied_by_designation, unit_implied_by_designation, bounds_implied_by_designation, default_implied_by_designation};
+
+/// View of a port that merges the data stated in the bundle with the data implied by the port's
+/// designations and properties. For example, a port designated as `lv2:enabled` is treated as a
+/// toggle with range 0 to 1 and default 1, and a port designated as `param:cutoffFrequency` is
+/// treated as logarithmic with unit Hertz. Data stated in the bundle always takes precedence over
+/// implied data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectivePort<'a> {
+    /// The underlying port description.
+    port: &'a PortInfo,
+
+    /// Stated and implied port properties.
+    port_properties: EnumSet<PortProperty>,
+
+    /// Stated unit, or failing that, the unit implied by the port's designations.
+    unit: Option<Unit>,
+
+    /// Numeric range, with implied properties, bounds and default applied.
+    control_range: ControlRange
+}
+
+impl<'a> EffectivePort<'a> {
+    /// Constructs the effective view of a port.
+    pub fn new(port: &'a PortInfo) -> Self {
+        let designations = port.designations;
+        let implied_properties = designations.iter()
+            .fold(EnumSet::empty(), |properties, designation| {
+                properties | port_properties_implied_by_designation(designation)
+            });
+        // Properties implied by designations are dropped if they conflict with stated ones, e.g. a
+        // frequency port stated as a toggle is not treated as logarithmic.
+        let stated_properties = port.all_port_properties();
+        let conflicting_properties = stated_properties.iter()
+            .fold(EnumSet::empty(), |properties, property| {
+                properties | PORT_PROPERTIES_CONFLICTING.adjacent_nodes(property)
+            });
+        let port_properties = PORT_PROPERTIES_IMPLIED.reachable_nodes_from_multi(stated_properties | (implied_properties - conflicting_properties));
+        let stated_range = port.control_range();
+        let bounds = designations.iter().find_map(bounds_implied_by_designation);
+        let default = designations.iter().find_map(default_implied_by_designation);
+        // The implied unit is only trusted if the stated bounds fit within the implied bounds.
+        // For example, a wet/dry ratio port with a maximum of 100 is a percentage, not a
+        // coefficient.
+        let fits_bounds = bounds.map_or(true, |(min, max)| {
+            stated_range.min().map_or(true, |stated| stated >= min)
+                && stated_range.max().map_or(true, |stated| stated <= max)
+        });
+        let unit = port.unit.or_else(|| designations.iter()
+            .find_map(unit_implied_by_designation)
+            .filter(|_| fits_bounds));
+
+        EffectivePort {
+            port,
+            port_properties,
+            unit,
+            control_range: stated_range.with_fallbacks(port_properties, bounds, default)
+        }
+    }
+
+    /// Gets the underlying port description.
+    pub fn port(&self) -> &'a PortInfo {
+        self.port
+    }
+
+    /// Gets the known port properties that apply to the port, whether stated or implied.
+    pub fn port_properties(&self) -> EnumSet<PortProperty> {
+        self.port_properties
+    }
+
+    /// Checks if a port property applies to the port, whether stated or implied.
+    pub fn has_port_property(&self, port_property: PortProperty) -> bool {
+        self.port_properties.contains(port_property)
+    }
+
+    /// Gets the unit of the port's value, whether stated or implied, or
+    /// [`None`](std::option::Option::None) if it is unknown.
+    pub fn unit(&self) -> Option<Unit> {
+        self.unit
+    }
+
+    /// Gets the numeric range of the port, with implied properties, bounds and default applied.
+    pub fn control_range(&self) -> &ControlRange {
+        &self.control_range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rdf_util::Graph;
+    use crate::bundle_model::constants::{IriConstant, PortType, PortDesignation, PortProperty, Unit};
+    use crate::bundle_model::loader::{load_plugin, LV2_DESIGNATION, LV2_MAXIMUM, LV2_PORT_PROPERTY, UNITS_UNIT};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node, add_plugin, add_port};
+
+    #[test]
+    fn designations_imply_properties_units_and_ranges() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let control_input = [PortType::InputPort.iri(), PortType::Control.iri()];
+        let enabled = add_port(&mut graph, &plugin, 0, "enabled", &control_input);
+        graph.insert(enabled, iri(LV2_DESIGNATION), iri_node(PortDesignation::Enabled.iri()));
+        let cutoff = add_port(&mut graph, &plugin, 1, "cutoff", &control_input);
+        graph.insert(cutoff.clone(), iri(LV2_DESIGNATION), iri_node(PortDesignation::CutoffFrequency.iri()));
+        graph.insert(cutoff, iri(UNITS_UNIT), iri_node(Unit::Kilohertz.iri()));
+        let mix = add_port(&mut graph, &plugin, 2, "mix", &control_input);
+        graph.insert(mix.clone(), iri(LV2_DESIGNATION), iri_node(PortDesignation::WetDryRatio.iri()));
+        graph.insert(mix, iri(LV2_MAXIMUM), literal_node("100"));
+        let dry_wet = add_port(&mut graph, &plugin, 3, "dry_wet", &control_input);
+        graph.insert(dry_wet, iri(LV2_DESIGNATION), iri_node(PortDesignation::WetDryRatio.iri()));
+        let frequency_switch = add_port(&mut graph, &plugin, 4, "frequency_switch", &control_input);
+        graph.insert(frequency_switch.clone(), iri(LV2_DESIGNATION), iri_node(PortDesignation::Frequency.iri()));
+        graph.insert(frequency_switch, iri(LV2_PORT_PROPERTY), iri_node(PortProperty::Trigger.iri()));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        let ports = plugin.ports();
+
+        let enabled = ports[0].effective();
+        assert!(enabled.has_port_property(PortProperty::Toggle));
+        let range = enabled.control_range();
+        assert_eq!((range.min(), range.default(), range.max()), (Some(0.0), Some(1.0), Some(1.0)));
+
+        let cutoff = ports[1].effective();
+        assert!(cutoff.has_port_property(PortProperty::Logarithmic));
+        assert_eq!(cutoff.unit(), Some(Unit::Kilohertz));
+
+        let frequency_switch = ports[4].effective();
+        assert!(frequency_switch.has_port_property(PortProperty::Toggle));
+        assert!(!frequency_switch.has_port_property(PortProperty::Logarithmic));
+
+        // Stated bounds that contradict the implied ones suppress both the implied bounds and
+        // the implied unit.
+        let mix = ports[2].effective();
+        assert_eq!(mix.unit(), None);
+        assert_eq!((mix.control_range().min(), mix.control_range().max()), (None, Some(100.0)));
+        assert!(!ports[2].has_port_property(PortProperty::Toggle));
+
+        let dry_wet = ports[3].effective();
+        assert_eq!(dry_wet.unit(), Some(Unit::Coefficient));
+        assert_eq!((dry_wet.control_range().min(), dry_wet.control_range().max()), (Some(0.0), Some(1.0)));
+    }
+}
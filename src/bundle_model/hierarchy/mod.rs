@@ -0,0 +1,132 @@
+//! Representation of the hierarchy of LV2 plugin types, including types that this crate doesn't
+//! understand.
+
+use std::collections::BTreeSet;
+use enumset::EnumSet;
+use crate::rdf_util::Iri;
+use crate::enum_graph::keyed::KeyedDiGraph;
+use crate::bundle_model::IdentifiedBy;
+use crate::bundle_model::constants::{IriConstant, PluginType};
+use crate::bundle_model::implications::PLUGIN_TYPE_SUBCLASSES;
+use crate::bundle_model::spec::SpecCatalog;
+use crate::bundle_model::loader::LV2_PLUGIN;
+
+/// Represents the hierarchy of plugin types, keyed by IRI. The subclass relationships among known
+/// [`PluginType`](crate::bundle_model::constants::PluginType)s are taken from the LV2
+/// specifications, and can be extended with subclass relationships involving
+/// [`UnknownPluginType`](crate::bundle_model::unknowns::UnknownPluginType)s, e.g. a vendor
+/// subclass of `lv2:FilterPlugin` discovered in a specification bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginTypeHierarchy {
+    /// Graph with an edge from each plugin type to each of its direct supertypes. Known plugin
+    /// types are keyed by their IRIs. `lv2:Plugin` itself is not a node, since every plugin type
+    /// is a subtype of it.
+    graph: KeyedDiGraph<Iri>
+}
+
+impl PluginTypeHierarchy {
+    /// Constructs a hierarchy containing only the subclass relationships among known plugin types.
+    pub fn new() -> Self {
+        PluginTypeHierarchy {
+            graph: KeyedDiGraph::from_enum_graph(&PLUGIN_TYPE_SUBCLASSES, known_iri)
+        }
+    }
+
+    /// Constructs a hierarchy containing the subclass relationships among known plugin types, plus the
+    /// subclass relationships among plugin types defined by a set of specifications. A class is
+    /// considered a plugin type if it is a subclass of `lv2:Plugin` or of a known plugin type.
+    pub fn with_spec_catalog(catalog: &SpecCatalog) -> Self {
+        let mut hierarchy = PluginTypeHierarchy::new();
+        let plugin_class = Iri::new(LV2_PLUGIN.to_string()).unwrap();
+        for class in catalog.classes_seq_iter() {
+            let is_plugin_type = catalog.is_subclass_of(class.id(), &plugin_class)
+                || !catalog.known_superclasses::<PluginType>(class.id()).is_empty();
+            if is_plugin_type {
+                for superclass in class.superclasses.iter().filter(|superclass| **superclass != plugin_class) {
+                    hierarchy.insert_subtype(class.id(), superclass);
+                }
+            }
+        }
+        hierarchy
+    }
+
+    /// Records that one plugin type is a direct subtype of another, i.e. that all plugins of the
+    /// first type are also of the second type.
+    pub fn insert_subtype(&mut self, subtype: &Iri, supertype: &Iri) {
+        self.graph.insert_edge(subtype.clone(), supertype.clone());
+    }
+
+    /// Gets all direct and indirect supertypes of a plugin type, identified by IRI. The type
+    /// itself is not included, and neither is `lv2:Plugin`.
+    pub fn supertypes(&self, plugin_type: &Iri) -> BTreeSet<Iri> {
+        self.graph.reachable_nodes(plugin_type).into_iter()
+            .filter(|supertype| supertype != plugin_type)
+            .collect()
+    }
+
+    /// Gets the known plugin types implied by a plugin type, including the type itself if it is
+    /// known.
+    pub fn known_supertypes(&self, plugin_type: &Iri) -> EnumSet<PluginType> {
+        self.graph.reachable_nodes(plugin_type).iter()
+            .filter_map(|iri| PluginType::from_iri(iri.as_str()))
+            .collect()
+    }
+
+    /// Checks if a plugin type is the same as, or a direct or indirect subtype of, another plugin
+    /// type.
+    pub fn is_subtype_of(&self, subtype: &Iri, supertype: &Iri) -> bool {
+        self.graph.has_path(subtype, supertype)
+    }
+}
+
+/// Gets the IRI identifying a known plugin type.
+pub(crate) fn known_iri(plugin_type: PluginType) -> Iri {
+    Iri::new(plugin_type.iri().to_string()).unwrap()
+}
+
+impl Default for PluginTypeHierarchy {
+    fn default() -> Self {
+        PluginTypeHierarchy::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdf_util::Graph;
+    use crate::bundle_model::loader::{RDF_TYPE, RDFS_SUBCLASS_OF, LV2_SPECIFICATION};
+    use crate::bundle_model::test_util::{iri, iri_node};
+
+    #[test]
+    fn vendor_subclasses_inherit_known_types() {
+        let mut graph = Graph::new();
+        graph.insert(iri_node("http://example.org/ext"), iri(RDF_TYPE), iri_node(LV2_SPECIFICATION));
+        graph.insert(iri_node("http://example.org/ext#Ladder"), iri(RDFS_SUBCLASS_OF),
+            iri_node(PluginType::Lowpass.iri()));
+        graph.insert(iri_node("http://example.org/ext#Sampler"), iri(RDFS_SUBCLASS_OF),
+            iri_node(LV2_PLUGIN));
+        graph.insert(iri_node("http://example.org/ext#Pane"), iri(RDFS_SUBCLASS_OF),
+            iri_node("http://example.org/ext#Widget"));
+        let mut catalog = SpecCatalog::new();
+        catalog.add_bundle(&graph).unwrap();
+        let hierarchy = PluginTypeHierarchy::with_spec_catalog(&catalog);
+
+        let ladder = iri("http://example.org/ext#Ladder");
+        assert_eq!(hierarchy.known_supertypes(&ladder), PluginType::Lowpass | PluginType::Filter);
+        assert_eq!(hierarchy.supertypes(&ladder),
+            vec![iri(PluginType::Filter.iri()), iri(PluginType::Lowpass.iri())].into_iter().collect());
+        assert!(hierarchy.is_subtype_of(&ladder, &iri(PluginType::Filter.iri())));
+        assert!(hierarchy.supertypes(&iri("http://example.org/ext#Sampler")).is_empty());
+        assert!(hierarchy.supertypes(&iri("http://example.org/ext#Pane")).is_empty());
+    }
+
+    #[test]
+    fn known_supertypes_exclude_the_type_itself() {
+        let hierarchy = PluginTypeHierarchy::new();
+        assert_eq!(hierarchy.supertypes(&iri(PluginType::Lowpass.iri())),
+            vec![iri(PluginType::Filter.iri())].into_iter().collect());
+        assert!(hierarchy.supertypes(&iri(PluginType::Filter.iri())).is_empty());
+        assert_eq!(hierarchy.known_supertypes(&iri(PluginType::Lowpass.iri())),
+            PluginType::Lowpass | PluginType::Filter);
+    }
+}
@@ -1,11 +1,13 @@
 //! Utilities to help with implementation of traits and/or other functionality.
 
 use enumset::{EnumSet, EnumSetIter, EnumSetType};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, btree_set};
+#[cfg(feature = "rayon")]
 use rayon::iter::{IterBridge, IntoParallelRefIterator, ParallelBridge};
 use crate::rdf_util::Literal;
 use crate::bundle_model::constants::{HostFeature, Lv2Option};
 use crate::bundle_model::unknowns::{UnknownHostFeature, UnknownOption};
+use crate::bundle_model::implications::HOST_FEATURES_IMPLIED;
 
 /// Represents a set that contains both "known" items and "unknown" items. The "unknown" items
 /// typically represent LV2 data that this crate does not understand, such as information from
@@ -24,8 +26,56 @@ pub(crate) struct KnownAndUnknownSet<K: EnumSetType, U> {
 }
 
 impl<K: EnumSetType, U> KnownAndUnknownSet<K, U> {
+    /// Constructs an empty set.
+    pub(crate) fn new() -> Self {
+        KnownAndUnknownSet {
+            knowns: EnumSet::empty(),
+            unknowns: BTreeSet::new()
+        }
+    }
+
+    /// Adds a "known" item to the set.
+    pub(crate) fn insert_known(&mut self, item: K) {
+        self.knowns.insert(item);
+    }
+
+    /// Adds an "unknown" item to the set.
+    pub(crate) fn insert_unknown(&mut self, item: U)
+        where U: Ord
+    {
+        self.unknowns.insert(item);
+    }
+
+    /// Gets the set of "known" items.
+    pub(crate) fn knowns(&self) -> EnumSet<K> {
+        self.knowns
+    }
+
+    /// Gets the set of "unknown" items.
+    pub(crate) fn unknowns(&self) -> &BTreeSet<U> {
+        &self.unknowns
+    }
+
+    /// Checks if the specified "known" item is in the set.
+    pub(crate) fn contains_known(&self, item: K) -> bool {
+        self.knowns.contains(item)
+    }
+
+    /// Gets a sequential iterator over the "known" elements of this set. The iterator will not
+    /// repeat elements.
+    pub(crate) fn knowns_seq_iter(&self) -> EnumSetIter<K> {
+        self.knowns.iter()
+    }
+
+    /// Gets a sequential iterator over the "unknown" elements of this set. The iterator will not
+    /// repeat elements.
+    pub(crate) fn unknowns_seq_iter(&self) -> btree_set::Iter<'_, U> {
+        self.unknowns.iter()
+    }
+
     /// Gets a (parallel) iterator over the "known" elements of this set. The iterator will not
     /// repeat elements.
+    #[cfg(feature = "rayon")]
     pub(crate) fn knowns_iter(&self) -> IterBridge<EnumSetIter<K>>
         where K: Send, EnumSet<K>: Send
     {
@@ -34,15 +84,22 @@ impl<K: EnumSetType, U> KnownAndUnknownSet<K, U> {
 
     /// Gets a (parallel) iterator over the "unknown" elements of this set. The iterator will not
     /// repeat elements.
-    pub(crate) fn unknowns_iter(&self) -> <BTreeSet<U> as IntoParallelRefIterator>::Iter
+    #[cfg(feature = "rayon")]
+    pub(crate) fn unknowns_iter(&self) -> <BTreeSet<U> as IntoParallelRefIterator<'_>>::Iter
         where U: Ord + Sync
     {
         self.unknowns.par_iter()
     }
 }
 
+impl<K: EnumSetType, U> Default for KnownAndUnknownSet<K, U> {
+    fn default() -> Self {
+        KnownAndUnknownSet::new()
+    }
+}
+
 /// Base functionality for implementing the [`Named`](crate::bundle_model::Named) trait.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub(crate) struct NamedImpl {
     /// Human-readable names. Multiple language-tagged literals can be used.
     pub(crate) names: BTreeSet<Literal>,
@@ -53,7 +110,7 @@ pub(crate) struct NamedImpl {
 }
 
 /// Base functionality for implementing the [`Documented`](crate::bundle_model::Documented) trait.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub(crate) struct DocumentedImpl {
     /// Documentation embedded in the bundle. Multiple language-tagged literals can be used. LV2
     /// specifies that the contents must be "a valid XHTML Basic 1.1 fragment suitable for use as
@@ -63,7 +120,7 @@ pub(crate) struct DocumentedImpl {
 
 /// Implements base functionality for requiring (and optionally supporting) LV2 host features and
 /// LV2 options.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub(crate) struct HostFeatureRequirer {
     /// Required host features. This set should not intersect with
     /// [`optional_host_features`](self::HostFeatureRequirer::optional_host_features).
@@ -80,4 +137,12 @@ pub(crate) struct HostFeatureRequirer {
     /// Optionally supported LV2 options. This set should not intersect with
     /// [`required_options`](self::HostFeatureRequirer::required_options)
     pub(crate) optional_options: KnownAndUnknownSet<Lv2Option, UnknownOption>
-}
\ No newline at end of file
+}
+
+impl HostFeatureRequirer {
+    /// Gets the known required host features, including the features they imply according to
+    /// [`HOST_FEATURES_IMPLIED`](crate::bundle_model::implications::HOST_FEATURES_IMPLIED).
+    pub(crate) fn all_required_host_features(&self) -> EnumSet<HostFeature> {
+        HOST_FEATURES_IMPLIED.reachable_nodes_from_multi(self.required_host_features.knowns())
+    }
+}
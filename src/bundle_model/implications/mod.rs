@@ -1,62 +1,75 @@
 //! Representation of implications in which the presence of one LV2 feature, property, etc. can
 //! imply the presence of others.
 
-// TODO: Explore possibility of generating some of the information in this module automatically at
-// build time, maybe using macros and/or RDF?
+// The direct implications, and the lookups from extension data and port designations, are generated
+// at build time from the vendored specification data and `data/implications.ttl` (see build.rs).
 
-use enum_map::EnumMap;
-use rayon::iter::FromParallelIterator;
-
-use crate::bundle_model::constants::{PluginType, PortDesignation, Unit};
+use enumset::EnumSet;
+use crate::bundle_model::constants::{PluginType, PortDesignation, Unit, HostFeature, PortProperty, ExtensionData};
 use crate::enum_graph::EnumSetDiGraph;
+use crate::enum_graph::labeled::LabeledEnumSetDiGraph;
+
+include!(concat!(env!("OUT_DIR"), "/implications.rs"));
+
+/// Directed graph defining the implications among plugin types. If an edge (p0, p1) exists in the
+/// graph, then all plugins of type p0 must also be of type p1. The graph is its own transitive
+/// closure, so there is no need to consider indirect paths when finding the implied plugin types.
+pub static PLUGIN_TYPES_IMPLIED: EnumSetDiGraph<PluginType> =
+    PLUGIN_TYPE_SUBCLASSES.transitive_closure();
+
+/// Directed graph defining the implications among host features. If an edge (f0, f1) exists in the
+/// graph, then an entity that requires f0 effectively requires f1 as well. The graph is its own
+/// transitive closure.
+pub static HOST_FEATURES_IMPLIED: EnumSetDiGraph<HostFeature> = HOST_FEATURE_IMPLICATIONS.transitive_closure();
+
+/// Directed graph defining the implications among port properties. If an edge (p0, p1) exists in
+/// the graph, then a port with property p0 should be treated as also having property p1. The graph
+/// is its own transitive closure.
+pub static PORT_PROPERTIES_IMPLIED: EnumSetDiGraph<PortProperty> = PORT_PROPERTY_IMPLICATIONS.transitive_closure();
+
+/// Directed graph defining the conflicts among port properties. If an edge (p0, p1) exists in the
+/// graph, then a port with property p0 should not be treated as having property p1 unless p1 is
+/// stated explicitly. The graph is symmetric.
+pub static PORT_PROPERTIES_CONFLICTING: EnumSetDiGraph<PortProperty> = PORT_PROPERTY_CONFLICTS;
+
+#[allow(deprecated)]
+pub use self::deprecated::UNITS_IMPLIED_BY_DESIGNATIONS;
+
+// Kept in a separate module because lazy_static expands to code that uses the deprecated item.
+#[allow(deprecated)]
+mod deprecated {
+    use enum_map::EnumMap;
+    use crate::bundle_model::constants::{PortDesignation, Unit};
+    use super::unit_implied_by_designation;
+
+    lazy_static! {
+        /// Maps LV2 port designations to the port units that they imply, if any.
+        #[deprecated(note = "use `unit_implied_by_designation` instead")]
+        pub static ref UNITS_IMPLIED_BY_DESIGNATIONS: EnumMap<PortDesignation, Option<Unit>> =
+            EnumMap::from(unit_implied_by_designation);
+    }
+}
+
+/// Relations between port properties, used as edge labels in
+/// [`port_property_relations`](self::port_property_relations).
+#[derive(Debug, EnumSetType, Enum)]
+pub enum PortPropertyRelation {
+    /// The first property implies the second (see
+    /// [`PORT_PROPERTIES_IMPLIED`](self::PORT_PROPERTIES_IMPLIED)).
+    Implies,
+
+    /// The first property conflicts with the second (see
+    /// [`PORT_PROPERTIES_CONFLICTING`](self::PORT_PROPERTIES_CONFLICTING)).
+    ConflictsWith
+}
 
-lazy_static! {
-    /// Directed graph defining the implications among plugin types. If an edge (p0, p1) exists in
-    /// the graph, then all plugins of type p0 must also be of type p1. The graph is its own
-    /// transitive closure, so there is no need to consider indirect paths when finding the implied
-    /// plugin types.
-    pub static ref PLUGIN_TYPES_IMPLIED: EnumSetDiGraph<PluginType> = {
-        let direct_implications = vec![
-            (PluginType::Reverb, PluginType::Delay),
-            (PluginType::Reverb, PluginType::Simulator),
-            (PluginType::Waveshaper, PluginType::Distortion),
-            (PluginType::Amplifier, PluginType::Dynamics),
-            (PluginType::Compressor, PluginType::Dynamics),
-            (PluginType::Envelope, PluginType::Dynamics),
-            (PluginType::Expander, PluginType::Dynamics),
-            (PluginType::Gate, PluginType::Dynamics),
-            (PluginType::Limiter, PluginType::Dynamics),
-            (PluginType::Allpass, PluginType::Filter),
-            (PluginType::Bandpass, PluginType::Filter),
-            (PluginType::Comb, PluginType::Filter),
-            (PluginType::EQ, PluginType::Filter),
-            (PluginType::Highpass, PluginType::Filter),
-            (PluginType::Lowpass, PluginType::Filter),
-            (PluginType::MultiEQ, PluginType::EQ),
-            (PluginType::ParaEQ, PluginType::EQ),
-            (PluginType::Constant, PluginType::Generator),
-            (PluginType::Instrument, PluginType::Generator),
-            (PluginType::Oscillator, PluginType::Generator),
-            (PluginType::Chorus, PluginType::Modulator),
-            (PluginType::Flanger, PluginType::Modulator),
-            (PluginType::Phaser, PluginType::Modulator),
-            (PluginType::Pitch, PluginType::Spectral),
-            (PluginType::Analyser, PluginType::Utility),
-            (PluginType::Converter, PluginType::Utility),
-            (PluginType::Function, PluginType::Utility),
-            (PluginType::Mixer, PluginType::Utility)
-        ];
-        EnumSetDiGraph::from_par_iter(direct_implications).transitive_closure()
-    };
-
-    /// Maps LV2 port designations to the port units that they imply, if any.
-    pub static ref UNITS_IMPLIED_BY_DESIGNATIONS: EnumMap<PortDesignation, Option<Unit>> = {
-        let implications = vec![
-            (PortDesignation::Gain, Unit::Decibel)
-        ];
-        let mut output = EnumMap::from(|_| None);
-        output.extend(implications.into_iter()
-            .map(|(designation, unit)| (designation, Some(unit))));
-        output
-    };
-}
\ No newline at end of file
+/// Gets the implications and conflicts among port properties as a single labeled graph. The graph
+/// is built on each call; code that only needs one relation should query
+/// [`PORT_PROPERTIES_IMPLIED`](self::PORT_PROPERTIES_IMPLIED) or
+/// [`PORT_PROPERTIES_CONFLICTING`](self::PORT_PROPERTIES_CONFLICTING) directly.
+pub fn port_property_relations() -> LabeledEnumSetDiGraph<PortProperty, PortPropertyRelation> {
+    let mut relations = LabeledEnumSetDiGraph::new();
+    relations.insert_graph(PortPropertyRelation::Implies, &PORT_PROPERTIES_IMPLIED);
+    relations.insert_graph(PortPropertyRelation::ConflictsWith, &PORT_PROPERTIES_CONFLICTING);
+    relations
+}
@@ -0,0 +1,750 @@
+//! Construction of bundle model objects from the RDF data in an LV2 bundle.
+//!
+//! This crate does not parse RDF syntax itself. The functions in this module read from a
+//! [`Graph`](crate::rdf_util::Graph) that has already been filled with the triples from a bundle's
+//! data files (e.g. `manifest.ttl` and the files it references).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use enumset::{EnumSet, EnumSetType};
+use num_bigint::BigUint;
+use ordered_float::OrderedFloat;
+use crate::rdf_util::{Graph, Iri, Literal, Node};
+use crate::bundle_model::{ResourceVersion, parse_version_number};
+use crate::bundle_model::constants::{IriConstant, PortDesignation, PortChannel, TimeDesignation, AtomType, PortType, Unit};
+use crate::bundle_model::unknowns::{UnknownPluginType, UnknownPortType, UnknownHostFeature, UnknownOption, UnknownExtensionData, UnknownPortProperty, UnknownPortDesignation};
+use crate::bundle_model::symbol::Symbol;
+use crate::bundle_model::impl_util::{KnownAndUnknownSet, NamedImpl, DocumentedImpl, HostFeatureRequirer};
+use crate::bundle_model::plugin::PluginInfo;
+use crate::bundle_model::port::{PortInfo, ScalePoint};
+use crate::bundle_model::project::ProjectInfo;
+use crate::bundle_model::midi::MidiBinding;
+use crate::bundle_model::spec::{SpecificationInfo, ClassInfo};
+
+pub(crate) const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+pub(crate) const RDF_VALUE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#value";
+pub(crate) const RDFS_LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+pub(crate) const RDFS_COMMENT: &str = "http://www.w3.org/2000/01/rdf-schema#comment";
+pub(crate) const RDFS_SUBCLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+pub(crate) const RDFS_CLASS: &str = "http://www.w3.org/2000/01/rdf-schema#Class";
+pub(crate) const RDFS_IS_DEFINED_BY: &str = "http://www.w3.org/2000/01/rdf-schema#isDefinedBy";
+pub(crate) const OWL_CLASS: &str = "http://www.w3.org/2002/07/owl#Class";
+pub(crate) const DOAP_NAME: &str = "http://usefulinc.com/ns/doap#name";
+pub(crate) const DOAP_SHORTNAME: &str = "http://usefulinc.com/ns/doap#shortname";
+pub(crate) const LV2_PLUGIN: &str = "http://lv2plug.in/ns/lv2core#Plugin";
+pub(crate) const LV2_SPECIFICATION: &str = "http://lv2plug.in/ns/lv2core#Specification";
+pub(crate) const LV2_BINARY: &str = "http://lv2plug.in/ns/lv2core#binary";
+pub(crate) const LV2_SYMBOL: &str = "http://lv2plug.in/ns/lv2core#symbol";
+pub(crate) const LV2_NAME: &str = "http://lv2plug.in/ns/lv2core#name";
+pub(crate) const LV2_SHORT_NAME: &str = "http://lv2plug.in/ns/lv2core#shortName";
+pub(crate) const LV2_DOCUMENTATION: &str = "http://lv2plug.in/ns/lv2core#documentation";
+pub(crate) const LV2_PROJECT: &str = "http://lv2plug.in/ns/lv2core#project";
+pub(crate) const LV2_MINOR_VERSION: &str = "http://lv2plug.in/ns/lv2core#minorVersion";
+pub(crate) const LV2_MICRO_VERSION: &str = "http://lv2plug.in/ns/lv2core#microVersion";
+pub(crate) const LV2_PROTOTYPE: &str = "http://lv2plug.in/ns/lv2core#prototype";
+pub(crate) const LV2_PORT: &str = "http://lv2plug.in/ns/lv2core#port";
+pub(crate) const LV2_INDEX: &str = "http://lv2plug.in/ns/lv2core#index";
+pub(crate) const LV2_DEFAULT: &str = "http://lv2plug.in/ns/lv2core#default";
+pub(crate) const LV2_MINIMUM: &str = "http://lv2plug.in/ns/lv2core#minimum";
+pub(crate) const LV2_MAXIMUM: &str = "http://lv2plug.in/ns/lv2core#maximum";
+pub(crate) const LV2_SCALE_POINT: &str = "http://lv2plug.in/ns/lv2core#scalePoint";
+pub(crate) const LV2_PORT_PROPERTY: &str = "http://lv2plug.in/ns/lv2core#portProperty";
+pub(crate) const LV2_DESIGNATION: &str = "http://lv2plug.in/ns/lv2core#designation";
+pub(crate) const LV2_EXTENSION_DATA: &str = "http://lv2plug.in/ns/lv2core#extensionData";
+pub(crate) const LV2_REQUIRED_FEATURE: &str = "http://lv2plug.in/ns/lv2core#requiredFeature";
+pub(crate) const LV2_OPTIONAL_FEATURE: &str = "http://lv2plug.in/ns/lv2core#optionalFeature";
+pub(crate) const OPTS_REQUIRED_OPTION: &str = "http://lv2plug.in/ns/ext/options#requiredOption";
+pub(crate) const OPTS_SUPPORTED_OPTION: &str = "http://lv2plug.in/ns/ext/options#supportedOption";
+pub(crate) const PPROPS_DISPLAY_PRIORITY: &str = "http://lv2plug.in/ns/ext/port-props#displayPriority";
+pub(crate) const PPROPS_RANGE_STEPS: &str = "http://lv2plug.in/ns/ext/port-props#rangeSteps";
+pub(crate) const RSZ_MINIMUM_SIZE: &str = "http://lv2plug.in/ns/ext/resize-port#minimumSize";
+pub(crate) const RSZ_AS_LARGE_AS: &str = "http://lv2plug.in/ns/ext/resize-port#asLargeAs";
+pub(crate) const ATOM_SUPPORTS: &str = "http://lv2plug.in/ns/ext/atom#supports";
+pub(crate) const XSD_HEX_BINARY: &str = "http://www.w3.org/2001/XMLSchema#hexBinary";
+pub(crate) const MIDI_BINDING: &str = "http://lv2plug.in/ns/ext/midi#binding";
+pub(crate) const MIDI_CHANNEL: &str = "http://lv2plug.in/ns/ext/midi#channel";
+pub(crate) const MIDI_CONTROLLER_NUMBER: &str = "http://lv2plug.in/ns/ext/midi#controllerNumber";
+pub(crate) const MORPH_SUPPORTS_TYPE: &str = "http://lv2plug.in/ns/ext/morph#supportsType";
+pub(crate) const MORPH_CURRENT_TYPE: &str = "http://lv2plug.in/ns/ext/morph#currentType";
+pub(crate) const UNITS_UNIT: &str = "http://lv2plug.in/ns/extensions/units#unit";
+pub(crate) const TIME_POSITION: &str = "http://lv2plug.in/ns/ext/time#Position";
+
+/// Error type returned when bundle RDF data cannot be converted into the bundle model.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LoadError {
+    /// A property that the bundle model requires is missing.
+    MissingProperty {
+        /// The node that is missing the property.
+        subject: Node,
+
+        /// IRI of the missing property.
+        property: &'static str
+    },
+
+    /// A property has a value that cannot be represented in the bundle model.
+    InvalidValue {
+        /// The node that has the property.
+        subject: Node,
+
+        /// IRI of the property.
+        property: &'static str,
+
+        /// The invalid property value.
+        value: Box<Node>
+    },
+
+    /// A plugin's port indices do not form the sequence `0, 1, ..., n-1` required by LV2.
+    InvalidPortIndices {
+        /// IRI identifying the plugin.
+        plugin: Iri
+    }
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            LoadError::MissingProperty { subject, property } =>
+                write!(f, "{:?} is missing required property <{}>", subject, property),
+            LoadError::InvalidValue { subject, property, value } =>
+                write!(f, "{:?} has invalid value {:?} for property <{}>", subject, value, property),
+            LoadError::InvalidPortIndices { plugin } =>
+                write!(f, "plugin <{}> has missing or duplicate port indices", plugin)
+        }
+    }
+}
+
+/// Gets the IRIs of all plugins described in the specified graph, i.e. all IRI nodes that have
+/// type `lv2:Plugin`. The returned list will not repeat IRIs.
+pub fn plugin_iris(graph: &Graph) -> Vec<&Iri> {
+    let plugin_class = Node::Iri(Iri::new(LV2_PLUGIN.to_string()).unwrap());
+    graph.subjects(RDF_TYPE, &plugin_class).filter_map(Node::as_iri).collect()
+}
+
+/// Loads descriptions of all plugins described in the specified graph.
+///
+/// # Errors
+/// Returns an error if any plugin description cannot be represented in the bundle model.
+pub fn load_plugins(graph: &Graph) -> Result<Vec<PluginInfo>, LoadError> {
+    plugin_iris(graph).into_iter().map(|iri| load_plugin(graph, iri)).collect()
+}
+
+/// Loads the description of the specified plugin. If the plugin has an `lv2:prototype`, the
+/// properties of the prototype are inherited. Prototypes may themselves have prototypes.
+/// Properties stated on the plugin itself override inherited ones, on a per-property basis. RDF
+/// doesn't order the values of a property, so if a resource has several prototypes, the one whose
+/// IRI sorts first takes precedence. There are two exceptions:
+/// - `rdf:type` values are combined, so that e.g. a plugin class stated on a prototype still
+///   applies.
+/// - `lv2:port` values are merged by port index, so that a plugin can override individual
+///   inherited ports.
+///
+/// # Parameters
+/// - `graph`: Graph containing the plugin description.
+/// - `plugin_iri`: IRI identifying the plugin.
+///
+/// # Errors
+/// Returns an error if the plugin description cannot be represented in the bundle model.
+pub fn load_plugin(graph: &Graph, plugin_iri: &Iri) -> Result<PluginInfo, LoadError> {
+    let subject = Node::Iri(plugin_iri.clone());
+    // The plugin's own properties are read from the expanded overlay, if there is one. Other
+    // resources (ports, the project, etc.) are read from the bundle graph.
+    let overlay = expand_prototypes(graph, &subject);
+    let plugin_graph = overlay.as_ref().unwrap_or(graph);
+
+    let binary = match plugin_graph.object(&subject, LV2_BINARY) {
+        Some(Node::Iri(iri)) => iri.clone(),
+        Some(other) => return Err(LoadError::InvalidValue {
+            subject,
+            property: LV2_BINARY,
+            value: Box::new(other.clone())
+        }),
+        None => return Err(LoadError::MissingProperty { subject, property: LV2_BINARY })
+    };
+
+    let mut ports = plugin_graph.objects(&subject, LV2_PORT)
+        .map(|port| load_port(graph, port))
+        .collect::<Result<Vec<_>, _>>()?;
+    ports.sort_by_key(|port| port.index);
+    if ports.iter().enumerate().any(|(expected, port)| port.index as usize != expected) {
+        return Err(LoadError::InvalidPortIndices { plugin: plugin_iri.clone() });
+    }
+
+    Ok(PluginInfo {
+        iri: plugin_iri.clone(),
+        symbol: load_symbol(plugin_graph, &subject)?,
+        binary,
+        plugin_types: load_iri_set(plugin_graph, &subject, RDF_TYPE, UnknownPluginType::new,
+            |iri| iri.as_str() == LV2_PLUGIN),
+        version: load_version(plugin_graph, &subject)?,
+        named_impl: load_named_impl(plugin_graph, &subject, DOAP_NAME, LV2_SHORT_NAME),
+        documented_impl: load_documented_impl(plugin_graph, &subject),
+        project: load_project(graph, &subject, plugin_graph.object(&subject, LV2_PROJECT))?,
+        ports,
+        provided_extension_data: load_iri_set(plugin_graph, &subject, LV2_EXTENSION_DATA,
+            UnknownExtensionData::new, |_| false),
+        host_feature_requirer: load_host_feature_requirer(plugin_graph, &subject)
+    })
+}
+
+/// Loads descriptions of all LV2 specifications described in the specified graph, i.e. all IRI
+/// nodes that have type `lv2:Specification`.
+///
+/// Every resource in the graph that has type `rdfs:Class` or `owl:Class`, or that has an
+/// `rdfs:subClassOf` property, is considered a class. A class belongs to the specification given
+/// by its `rdfs:isDefinedBy` property, or failing that, to the specification whose IRI is a prefix
+/// of the class IRI (e.g. `http://lv2plug.in/ns/ext/atom#Atom` belongs to
+/// `http://lv2plug.in/ns/ext/atom`). Classes that belong to no specification in the graph are
+/// ignored.
+///
+/// # Errors
+/// Returns an error if any specification description cannot be represented in the bundle model.
+pub fn load_specifications(graph: &Graph) -> Result<Vec<SpecificationInfo>, LoadError> {
+    let spec_class = Node::Iri(Iri::new(LV2_SPECIFICATION.to_string()).unwrap());
+    let mut specs = graph.subjects(RDF_TYPE, &spec_class)
+        .filter_map(Node::as_iri)
+        .map(|iri| {
+            let subject = Node::Iri(iri.clone());
+            Ok(SpecificationInfo {
+                iri: iri.clone(),
+                version: load_version(graph, &subject)?,
+                labels: load_literals(graph, &subject, RDFS_LABEL),
+                classes: BTreeMap::new()
+            })
+        })
+        .collect::<Result<Vec<_>, LoadError>>()?;
+
+    let class_nodes: BTreeSet<&Node> = [RDFS_CLASS, OWL_CLASS].iter()
+        .flat_map(|class_type| {
+            let class_type = Node::Iri(Iri::new(class_type.to_string()).unwrap());
+            graph.subjects(RDF_TYPE, &class_type).collect::<Vec<_>>()
+        })
+        .chain(graph.subjects_with_predicate(RDFS_SUBCLASS_OF))
+        .collect();
+    for class_iri in class_nodes.into_iter().filter_map(Node::as_iri) {
+        let subject = Node::Iri(class_iri.clone());
+        let defined_by = graph.object(&subject, RDFS_IS_DEFINED_BY).and_then(Node::as_iri);
+        let owner = specs.iter_mut().find(|spec| match defined_by {
+            Some(defined_by) => *defined_by == spec.iri,
+            None => class_iri.as_str().strip_prefix(spec.iri.as_str())
+                .is_some_and(|rest| rest.starts_with('#') || rest.starts_with('/'))
+        });
+        if let Some(spec) = owner {
+            spec.classes.insert(class_iri.clone(), ClassInfo {
+                iri: class_iri.clone(),
+                labels: load_literals(graph, &subject, RDFS_LABEL),
+                superclasses: graph.objects(&subject, RDFS_SUBCLASS_OF).filter_map(Node::as_iri).cloned().collect()
+            });
+        }
+    }
+    Ok(specs)
+}
+
+/// Applies `lv2:prototype` inheritance to a resource, as described in
+/// [`load_plugin`](self::load_plugin). Returns a small graph containing only the triples whose
+/// subject is the resource, including inherited ones, or [`None`](std::option::Option::None) if
+/// the resource has no prototype. Other resources (e.g. inherited ports) are not copied, and
+/// should still be read from the original graph.
+fn expand_prototypes(graph: &Graph, subject: &Node) -> Option<Graph> {
+    graph.object(subject, LV2_PROTOTYPE)?;
+
+    let mut expanded = Graph::new();
+    for (predicate, objects) in inherited_properties(graph, subject, &mut BTreeSet::new()) {
+        for object in objects {
+            expanded.insert(subject.clone(), predicate.clone(), object);
+        }
+    }
+    Some(expanded)
+}
+
+/// Collects the properties of a resource, including those inherited from its prototypes (see
+/// [`expand_prototypes`](self::expand_prototypes)). The `lv2:prototype` property itself is not
+/// included. Resources in `visited` are not expanded again, which guards against prototype cycles.
+fn inherited_properties(graph: &Graph, subject: &Node, visited: &mut BTreeSet<Node>)
+    -> BTreeMap<Iri, BTreeSet<Node>>
+{
+    visited.insert(subject.clone());
+    let mut properties: BTreeMap<Iri, BTreeSet<Node>> = graph.predicates(subject)
+        .filter(|predicate| predicate.as_str() != LV2_PROTOTYPE)
+        .map(|predicate| (predicate.clone(), graph.objects(subject, predicate.as_str()).cloned().collect()))
+        .collect();
+
+    let prototypes: Vec<Node> = graph.objects(subject, LV2_PROTOTYPE)
+        .filter(|prototype| prototype.as_literal().is_none())
+        .cloned()
+        .collect();
+    for prototype in prototypes {
+        if visited.contains(&prototype) {
+            continue;
+        }
+        for (predicate, objects) in inherited_properties(graph, &prototype, visited) {
+            match predicate.as_str() {
+                RDF_TYPE => properties.entry(predicate).or_default().extend(objects),
+                LV2_PORT => {
+                    let ports = properties.entry(predicate).or_default();
+                    let indices: BTreeSet<u32> = ports.iter()
+                        .filter_map(|port| port_index(graph, port))
+                        .collect();
+                    let inherited: Vec<Node> = objects.into_iter()
+                        .filter(|port| !port_index(graph, port).is_some_and(|index| indices.contains(&index)))
+                        .collect();
+                    ports.extend(inherited);
+                },
+                _ => {
+                    properties.entry(predicate).or_insert(objects);
+                }
+            }
+        }
+    }
+    properties
+}
+
+/// Gets the `lv2:index` of a port, or [`None`](std::option::Option::None) if it is missing or
+/// invalid. Errors are left to [`load_port`](self::load_port).
+fn port_index(graph: &Graph, port: &Node) -> Option<u32> {
+    graph.object(port, LV2_INDEX)?.as_literal()?.value().trim().parse().ok()
+}
+
+/// Loads the version of a resource from its `lv2:minorVersion` and `lv2:microVersion`
+/// properties. A missing version number is treated as zero, so a resource with no version
+/// information is considered to have version 0.0.
+pub(crate) fn load_version(graph: &Graph, subject: &Node) -> Result<ResourceVersion, LoadError> {
+    let load_number = |property| match load_literal(graph, subject, property)? {
+        None => Ok(0u8.into()),
+        Some(literal) => parse_version_number(literal).map_err(|_| LoadError::InvalidValue {
+            subject: subject.clone(),
+            property,
+            value: Box::new(Node::Literal(literal.clone()))
+        })
+    };
+    Ok(ResourceVersion::new(load_number(LV2_MINOR_VERSION)?, load_number(LV2_MICRO_VERSION)?))
+}
+
+/// Loads the description of a port.
+fn load_port(graph: &Graph, subject: &Node) -> Result<PortInfo, LoadError> {
+    let index = match load_literal(graph, subject, LV2_INDEX)? {
+        Some(literal) => literal.value().trim().parse::<u32>().map_err(|_| LoadError::InvalidValue {
+            subject: subject.clone(),
+            property: LV2_INDEX,
+            value: Box::new(Node::Literal(literal.clone()))
+        })?,
+        None => return Err(LoadError::MissingProperty { subject: subject.clone(), property: LV2_INDEX })
+    };
+
+    let mut designations = EnumSet::empty();
+    let mut channel_designations = EnumSet::empty();
+    let mut time_designations = EnumSet::empty();
+    let mut unknown_designations = BTreeSet::new();
+    for designation in graph.objects(subject, LV2_DESIGNATION) {
+        let iri = load_iri_value(subject, LV2_DESIGNATION, designation)?;
+        if let Some(known) = PortDesignation::from_iri(iri.as_str()) {
+            designations.insert(known);
+        }
+        else if let Some(known) = PortChannel::from_iri(iri.as_str()) {
+            channel_designations.insert(known);
+        }
+        else if let Some(known) = TimeDesignation::from_iri(iri.as_str()) {
+            time_designations.insert(known);
+        }
+        else {
+            unknown_designations.insert(UnknownPortDesignation::new(iri.clone()));
+        }
+    }
+
+    Ok(PortInfo {
+        port_types: load_iri_set(graph, subject, RDF_TYPE, UnknownPortType::new,
+            |iri| iri.as_str() == "http://lv2plug.in/ns/lv2core#Port"),
+        index,
+        symbol: load_symbol(graph, subject)?,
+        named_impl: load_named_impl(graph, subject, LV2_NAME, LV2_SHORT_NAME),
+        documented_impl: load_documented_impl(graph, subject),
+        default_value: load_literal(graph, subject, LV2_DEFAULT)?.cloned(),
+        max_value: load_literal(graph, subject, LV2_MAXIMUM)?.cloned(),
+        min_value: load_literal(graph, subject, LV2_MINIMUM)?.cloned(),
+        scale_pts: graph.objects(subject, LV2_SCALE_POINT)
+            .map(|scale_pt| load_scale_point(graph, scale_pt))
+            .collect::<Result<_, _>>()?,
+        port_props: load_iri_set(graph, subject, LV2_PORT_PROPERTY, UnknownPortProperty::new,
+            |_| false),
+        designations,
+        channel_designations,
+        time_designations,
+        unknown_designations,
+        display_priority: load_biguint(graph, subject, PPROPS_DISPLAY_PRIORITY)?,
+        range_steps: load_biguint(graph, subject, PPROPS_RANGE_STEPS)?,
+        min_buffer_size: load_biguint(graph, subject, RSZ_MINIMUM_SIZE)?,
+        buffer_as_large_as: graph.objects(subject, RSZ_AS_LARGE_AS)
+            .map(|symbol| load_symbol_value(subject, RSZ_AS_LARGE_AS, symbol))
+            .collect::<Result<_, _>>()?,
+        supports_time_position: graph.objects(subject, ATOM_SUPPORTS)
+            .filter_map(Node::as_iri)
+            .any(|iri| iri.as_str() == TIME_POSITION),
+        midi_binding: graph.objects(subject, MIDI_BINDING)
+            .find_map(|binding| load_midi_binding(graph, binding)),
+        morph_supported_types: load_iri_set(graph, subject, MORPH_SUPPORTS_TYPE,
+            UnknownPortType::new, |_| false),
+        morph_current_type: match graph.object(subject, MORPH_CURRENT_TYPE) {
+            Some(current_type) =>
+                PortType::from_iri(load_iri_value(subject, MORPH_CURRENT_TYPE, current_type)?.as_str()),
+            None => None
+        },
+        unit: graph.objects(subject, UNITS_UNIT)
+            .filter_map(Node::as_iri)
+            .find_map(|iri| Unit::from_iri(iri.as_str()))
+    })
+}
+
+/// Loads a port's MIDI binding. The binding may be either a raw MIDI message literal in
+/// hexadecimal (with datatype `midi:MidiEvent` or `xsd:hexBinary`), or a node describing the
+/// message with its type, `midi:channel` and `midi:controllerNumber` properties. Since a MIDI
+/// binding is only a suggestion, a binding that cannot be represented (e.g. a literal of another
+/// datatype, a malformed literal or an out-of-range channel) is ignored rather than treated as an
+/// error.
+fn load_midi_binding(graph: &Graph, binding: &Node) -> Option<MidiBinding> {
+    if let Node::Literal(literal) = binding {
+        let data_type = literal.data_type().as_str();
+        if data_type != AtomType::MidiEvent.iri() && data_type != XSD_HEX_BINARY {
+            return None;
+        }
+        let hex: String = literal.value().chars().filter(|c| !c.is_whitespace()).collect();
+        if hex.len() % 2 != 0 || !hex.is_ascii() {
+            return None;
+        }
+        let message = (0..hex.len()).step_by(2)
+            .map(|start| u8::from_str_radix(&hex[start..start + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        return MidiBinding::from_raw_message(&message).ok();
+    }
+
+    let message_type = graph.objects(binding, RDF_TYPE)
+        .filter_map(Node::as_iri)
+        .filter_map(|iri| AtomType::from_iri(iri.as_str()))
+        .find(|message_type| MidiBinding::new(*message_type, None, None).is_ok())?;
+    let load_byte = |property| match graph.object(binding, property) {
+        None => Some(None),
+        Some(value) => value.as_literal()
+            .and_then(|literal| literal.value().trim().parse::<u8>().ok())
+            .map(Some)
+    };
+    MidiBinding::new(message_type, load_byte(MIDI_CHANNEL)?, load_byte(MIDI_CONTROLLER_NUMBER)?).ok()
+}
+
+/// Loads a scale point.
+fn load_scale_point(graph: &Graph, subject: &Node) -> Result<ScalePoint, LoadError> {
+    let value = match load_literal(graph, subject, RDF_VALUE)? {
+        Some(literal) => literal.to_f32().ok_or_else(|| LoadError::InvalidValue {
+            subject: subject.clone(),
+            property: RDF_VALUE,
+            value: Box::new(Node::Literal(literal.clone()))
+        })?,
+        None => return Err(LoadError::MissingProperty { subject: subject.clone(), property: RDF_VALUE })
+    };
+
+    Ok(ScalePoint {
+        labels: load_literals(graph, subject, RDFS_LABEL),
+        value: OrderedFloat(value)
+    })
+}
+
+/// Loads the project referenced by a resource's `lv2:project` property, if any.
+///
+/// # Parameters
+/// - `graph`: Graph containing the project description.
+/// - `subject`: The resource that references the project.
+/// - `project`: Value of the resource's `lv2:project` property, if any.
+fn load_project(graph: &Graph, subject: &Node, project: Option<&Node>)
+    -> Result<Option<ProjectInfo>, LoadError>
+{
+    match project {
+        None => Ok(None),
+        Some(Node::Literal(literal)) => Err(LoadError::InvalidValue {
+            subject: subject.clone(),
+            property: LV2_PROJECT,
+            value: Box::new(Node::Literal(literal.clone()))
+        }),
+        Some(project) => Ok(Some(ProjectInfo {
+            iri: project.as_iri().cloned(),
+            symbol: load_symbol(graph, project)?,
+            named_impl: load_named_impl(graph, project, DOAP_NAME, DOAP_SHORTNAME)
+        }))
+    }
+}
+
+/// Loads the required and optional host features and LV2 options of a resource.
+pub(crate) fn load_host_feature_requirer(graph: &Graph, subject: &Node) -> HostFeatureRequirer {
+    HostFeatureRequirer {
+        required_host_features: load_iri_set(graph, subject, LV2_REQUIRED_FEATURE,
+            UnknownHostFeature::new, |_| false),
+        optional_host_features: load_iri_set(graph, subject, LV2_OPTIONAL_FEATURE,
+            UnknownHostFeature::new, |_| false),
+        required_options: load_iri_set(graph, subject, OPTS_REQUIRED_OPTION, UnknownOption::new,
+            |_| false),
+        optional_options: load_iri_set(graph, subject, OPTS_SUPPORTED_OPTION, UnknownOption::new,
+            |_| false)
+    }
+}
+
+/// Loads name and short name information for a resource.
+fn load_named_impl(graph: &Graph, subject: &Node, name_property: &str, short_name_property: &str)
+    -> NamedImpl
+{
+    NamedImpl {
+        names: load_literals(graph, subject, name_property),
+        short_names: load_literals(graph, subject, short_name_property)
+    }
+}
+
+/// Loads documentation information for a resource. Both `lv2:documentation` and `rdfs:comment`
+/// are accepted as documentation.
+fn load_documented_impl(graph: &Graph, subject: &Node) -> DocumentedImpl {
+    let mut documentation = load_literals(graph, subject, LV2_DOCUMENTATION);
+    if documentation.is_empty() {
+        documentation = load_literals(graph, subject, RDFS_COMMENT);
+    }
+    DocumentedImpl {
+        documentation
+    }
+}
+
+/// Loads the set of IRIs referenced by a property, splitting them into known and unknown items.
+/// Non-IRI values are ignored, as are IRIs for which `ignore` returns true.
+pub(crate) fn load_iri_set<K, U, F, I>(graph: &Graph, subject: &Node, property: &str,
+    make_unknown: F, ignore: I) -> KnownAndUnknownSet<K, U>
+    where K: EnumSetType + IriConstant, U: Ord, F: Fn(Iri) -> U, I: Fn(&Iri) -> bool
+{
+    let mut output = KnownAndUnknownSet::new();
+    for iri in graph.objects(subject, property).filter_map(Node::as_iri) {
+        if ignore(iri) {
+            continue;
+        }
+        match K::from_iri(iri.as_str()) {
+            Some(known) => output.insert_known(known),
+            None => output.insert_unknown(make_unknown(iri.clone()))
+        }
+    }
+    output
+}
+
+/// Loads all literal values of a property. Non-literal values are ignored.
+pub(crate) fn load_literals(graph: &Graph, subject: &Node, property: &str) -> BTreeSet<Literal> {
+    graph.objects(subject, property).filter_map(Node::as_literal).cloned().collect()
+}
+
+/// Loads a literal-valued property that should have at most one value.
+pub(crate) fn load_literal<'a>(graph: &'a Graph, subject: &Node, property: &'static str)
+    -> Result<Option<&'a Literal>, LoadError>
+{
+    match graph.object(subject, property) {
+        None => Ok(None),
+        Some(Node::Literal(literal)) => Ok(Some(literal)),
+        Some(other) => Err(LoadError::InvalidValue {
+            subject: subject.clone(),
+            property,
+            value: Box::new(other.clone())
+        })
+    }
+}
+
+/// Loads a property whose value should be a non-negative integer literal.
+pub(crate) fn load_biguint(graph: &Graph, subject: &Node, property: &'static str)
+    -> Result<Option<BigUint>, LoadError>
+{
+    match load_literal(graph, subject, property)? {
+        None => Ok(None),
+        Some(literal) => literal.value().trim().parse().map(Some).map_err(|_| LoadError::InvalidValue {
+            subject: subject.clone(),
+            property,
+            value: Box::new(Node::Literal(literal.clone()))
+        })
+    }
+}
+
+/// Loads a resource's `lv2:symbol` property, if present.
+fn load_symbol(graph: &Graph, subject: &Node) -> Result<Option<Symbol>, LoadError> {
+    graph.object(subject, LV2_SYMBOL)
+        .map(|symbol| load_symbol_value(subject, LV2_SYMBOL, symbol))
+        .transpose()
+}
+
+/// Converts a property value into an LV2 symbol.
+fn load_symbol_value(subject: &Node, property: &'static str, value: &Node)
+    -> Result<Symbol, LoadError>
+{
+    value.as_literal()
+        .and_then(|literal| Symbol::new(literal.value().to_string()).ok())
+        .ok_or_else(|| LoadError::InvalidValue {
+            subject: subject.clone(),
+            property,
+            value: Box::new(value.clone())
+        })
+}
+
+/// Converts a property value into an IRI.
+fn load_iri_value<'a>(subject: &Node, property: &'static str, value: &'a Node)
+    -> Result<&'a Iri, LoadError>
+{
+    value.as_iri().ok_or_else(|| LoadError::InvalidValue {
+        subject: subject.clone(),
+        property,
+        value: Box::new(value.clone())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle_model::{HasRelatedSet, NameRelation};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node, add_plugin, add_port};
+
+    #[test]
+    fn plugins_inherit_from_prototypes() {
+        let mut graph = Graph::new();
+        let prototype = iri_node("urn:test:family");
+        graph.insert(prototype.clone(), iri(LV2_BINARY), iri_node("family.so"));
+        graph.insert(prototype.clone(), iri(DOAP_NAME), literal_node("Family"));
+        graph.insert(prototype.clone(), iri(LV2_MINOR_VERSION), literal_node("2"));
+        add_port(&mut graph, &prototype, 0, "in", &[PortType::InputPort.iri(), PortType::Audio.iri()]);
+        add_port(&mut graph, &prototype, 1, "gain", &[PortType::InputPort.iri(), PortType::Control.iri()]);
+
+        let plugin = iri_node("urn:test:member");
+        graph.insert(plugin.clone(), iri(RDF_TYPE), iri_node(LV2_PLUGIN));
+        graph.insert(plugin.clone(), iri(LV2_PROTOTYPE), prototype.clone());
+        graph.insert(plugin.clone(), iri(DOAP_NAME), literal_node("Member"));
+        let port = Node::BlankNode(String::from("member_gain"));
+        graph.insert(plugin, iri(LV2_PORT), port.clone());
+        // Indices are compared as numbers, so this port replaces the prototype's port 1.
+        graph.insert(port.clone(), iri(LV2_INDEX), literal_node("01"));
+        graph.insert(port.clone(), iri(LV2_SYMBOL), literal_node("level"));
+        graph.insert(port, iri(RDF_TYPE), iri_node(PortType::InputPort.iri()));
+        // A prototype cycle must not prevent loading.
+        graph.insert(prototype, iri(LV2_PROTOTYPE), iri_node("urn:test:member"));
+
+        let info = load_plugin(&graph, &iri("urn:test:member")).unwrap();
+        let names: Vec<_> = HasRelatedSet::<NameRelation, Literal>::seq_set_iter(&info)
+            .map(|name| name.value().to_string()).collect();
+        assert_eq!(names, vec![String::from("Member")]);
+        assert_eq!(info.binary.as_str(), "family.so");
+        assert_eq!(info.version, ResourceVersion::new(2u8.into(), 0u8.into()));
+        let symbols: Vec<_> = info.ports().iter()
+            .map(|port| port.symbol.as_ref().unwrap().string()).collect();
+        assert_eq!(symbols, vec!["in", "level"]);
+
+        // Only the plugin's own triples are copied.
+        let overlay = expand_prototypes(&graph, &iri_node("urn:test:member")).unwrap();
+        assert_eq!(overlay.predicates(&Node::BlankNode(String::from("member_gain"))).count(), 0);
+        assert_eq!(overlay.predicates(&iri_node("urn:test:family")).count(), 0);
+        assert!(expand_prototypes(&graph, &Node::BlankNode(String::from("member_gain"))).is_none());
+    }
+
+    #[test]
+    fn midi_bindings_are_loaded_from_literals_and_nodes() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let types = [PortType::InputPort.iri(), PortType::Control.iri()];
+        let literal_port = add_port(&mut graph, &plugin, 0, "cutoff", &types);
+        let midi_event = iri(AtomType::MidiEvent.iri());
+        graph.insert(literal_port, iri(MIDI_BINDING),
+            Node::Literal(Literal::with_data_type(String::from("B0 4A"), midi_event).unwrap()));
+        let node_port = add_port(&mut graph, &plugin, 1, "volume", &types);
+        let binding = Node::BlankNode(String::from("volume_binding"));
+        graph.insert(node_port, iri(MIDI_BINDING), binding.clone());
+        graph.insert(binding.clone(), iri(RDF_TYPE), iri_node(AtomType::MidiController.iri()));
+        graph.insert(binding.clone(), iri(MIDI_CHANNEL), literal_node("2"));
+        graph.insert(binding, iri(MIDI_CONTROLLER_NUMBER), literal_node("7"));
+        let hex_port = add_port(&mut graph, &plugin, 2, "pan", &types);
+        graph.insert(hex_port, iri(MIDI_BINDING),
+            Node::Literal(Literal::with_data_type(String::from("B10A"), iri(XSD_HEX_BINARY)).unwrap()));
+        // Plain strings are not decoded, even if they look like hexadecimal.
+        let string_port = add_port(&mut graph, &plugin, 3, "resonance", &types);
+        graph.insert(string_port, iri(MIDI_BINDING), literal_node("B0 47"));
+
+        let info = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        let bindings: Vec<_> = info.ports().iter()
+            .map(|port| port.midi_binding()
+                .map(|binding| (binding.message_type(), binding.channel(), binding.controller_number())))
+            .collect();
+        assert_eq!(bindings, vec![
+            Some((AtomType::MidiController, Some(0), Some(0x4a))),
+            Some((AtomType::MidiController, Some(2), Some(7))),
+            Some((AtomType::MidiController, Some(1), Some(0x0a))),
+            None
+        ]);
+    }
+
+    #[test]
+    fn invalid_midi_bindings_are_ignored() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let types = [PortType::InputPort.iri(), PortType::Control.iri()];
+        let malformed = add_port(&mut graph, &plugin, 0, "malformed", &types);
+        graph.insert(malformed, iri(MIDI_BINDING), literal_node("B0 4"));
+        let unsupported = add_port(&mut graph, &plugin, 1, "unsupported", &types);
+        graph.insert(unsupported, iri(MIDI_BINDING), literal_node("F8"));
+        let out_of_range = add_port(&mut graph, &plugin, 2, "out_of_range", &types);
+        let binding = Node::BlankNode(String::from("out_of_range_binding"));
+        graph.insert(out_of_range, iri(MIDI_BINDING), binding.clone());
+        graph.insert(binding.clone(), iri(RDF_TYPE), iri_node(AtomType::MidiController.iri()));
+        graph.insert(binding, iri(MIDI_CHANNEL), literal_node("16"));
+
+        let info = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        assert_eq!(info.ports().len(), 3);
+        assert!(info.ports().iter().all(|port| port.midi_binding().is_none()));
+    }
+
+    #[test]
+    fn missing_required_properties_are_reported() {
+        let mut graph = Graph::new();
+        let plugin = iri_node("urn:test:plugin");
+        graph.insert(plugin.clone(), iri(RDF_TYPE), iri_node(LV2_PLUGIN));
+        assert_eq!(load_plugin(&graph, &iri("urn:test:plugin")),
+            Err(LoadError::MissingProperty { subject: plugin.clone(), property: LV2_BINARY }));
+
+        graph.insert(plugin.clone(), iri(LV2_BINARY), iri_node("plugin.so"));
+        let port = Node::BlankNode(String::from("port"));
+        graph.insert(plugin, iri(LV2_PORT), port.clone());
+        assert_eq!(load_plugin(&graph, &iri("urn:test:plugin")),
+            Err(LoadError::MissingProperty { subject: port, property: LV2_INDEX }));
+    }
+
+    #[test]
+    fn invalid_index_and_symbol_literals_are_reported() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let port = Node::BlankNode(String::from("port"));
+        graph.insert(plugin.clone(), iri(LV2_PORT), port.clone());
+        graph.insert(port.clone(), iri(LV2_INDEX), literal_node("first"));
+        assert_eq!(load_plugin(&graph, &iri("urn:test:plugin")), Err(LoadError::InvalidValue {
+            subject: port.clone(),
+            property: LV2_INDEX,
+            value: Box::new(literal_node("first"))
+        }));
+
+        graph.remove_property(&port, LV2_INDEX);
+        graph.insert(port, iri(LV2_INDEX), literal_node("0"));
+        graph.insert(plugin.clone(), iri(LV2_SYMBOL), literal_node("1st"));
+        assert_eq!(load_plugin(&graph, &iri("urn:test:plugin")), Err(LoadError::InvalidValue {
+            subject: plugin,
+            property: LV2_SYMBOL,
+            value: Box::new(literal_node("1st"))
+        }));
+    }
+
+    #[test]
+    fn duplicate_plugins_and_port_indices() {
+        let mut graph = Graph::new();
+        add_plugin(&mut graph, "urn:test:plugin");
+        let mut other_file = Graph::new();
+        let plugin = add_plugin(&mut other_file, "urn:test:plugin");
+        add_port(&mut other_file, &plugin, 0, "in", &[PortType::InputPort.iri(), PortType::Audio.iri()]);
+        graph.extend(&other_file);
+        assert_eq!(plugin_iris(&graph), vec![&iri("urn:test:plugin")]);
+        assert_eq!(load_plugins(&graph).unwrap().len(), 1);
+
+        let duplicate = Node::BlankNode(String::from("duplicate"));
+        graph.insert(plugin, iri(LV2_PORT), duplicate.clone());
+        graph.insert(duplicate, iri(LV2_INDEX), literal_node("0"));
+        assert_eq!(load_plugins(&graph),
+            Err(LoadError::InvalidPortIndices { plugin: iri("urn:test:plugin") }));
+    }
+}
@@ -0,0 +1,153 @@
+//! Representation of MIDI bindings, i.e. suggested MIDI controller mappings for ports.
+
+use crate::bundle_model::constants::AtomType;
+
+/// Error type returned when trying to construct an invalid MIDI binding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InvalidMidiBindingError {
+    /// The message type is not a MIDI message type that can be bound to a port.
+    MessageType(AtomType),
+
+    /// The MIDI channel is outside the range 0 to 15.
+    Channel(u8),
+
+    /// The controller number is outside the range 0 to 127.
+    ControllerNumber(u8),
+
+    /// The raw MIDI message is empty or does not start with a channel voice status byte.
+    RawMessage(Vec<u8>)
+}
+
+/// Represents a MIDI binding for a port, as specified by the `midi:binding` property. A MIDI
+/// binding describes the MIDI message that a host should (by default) map to the port, e.g. a
+/// particular controller on a particular channel. Hosts can use this to offer default mappings for
+/// hardware controllers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MidiBinding {
+    /// Type of MIDI message. Always one of the MIDI channel voice message types.
+    message_type: AtomType,
+
+    /// MIDI channel (0 to 15), if the binding is restricted to a channel.
+    channel: Option<u8>,
+
+    /// Controller number (0 to 127), if specified. Only meaningful for controller messages.
+    controller_number: Option<u8>
+}
+
+impl MidiBinding {
+    /// Constructs a MIDI binding.
+    ///
+    /// # Parameters
+    /// - `message_type`: Type of MIDI message. Must be a MIDI channel voice message type, i.e.
+    ///   [`MidiVoiceMessage`](crate::bundle_model::constants::AtomType::MidiVoiceMessage) or one of
+    ///   its subtypes.
+    /// - `channel`: MIDI channel (0 to 15), or [`None`](std::option::Option::None) to bind to all
+    ///   channels.
+    /// - `controller_number`: Controller number (0 to 127), or
+    ///   [`None`](std::option::Option::None) if not specified.
+    ///
+    /// # Errors
+    /// Returns an error if any parameter is out of range.
+    pub fn new(message_type: AtomType, channel: Option<u8>, controller_number: Option<u8>)
+        -> Result<Self, InvalidMidiBindingError>
+    {
+        if !is_voice_message_type(message_type) {
+            return Err(InvalidMidiBindingError::MessageType(message_type));
+        }
+        if let Some(channel) = channel.filter(|channel| *channel > 15) {
+            return Err(InvalidMidiBindingError::Channel(channel));
+        }
+        if let Some(number) = controller_number.filter(|number| *number > 127) {
+            return Err(InvalidMidiBindingError::ControllerNumber(number));
+        }
+
+        Ok(MidiBinding {
+            message_type,
+            channel,
+            controller_number
+        })
+    }
+
+    /// Constructs a MIDI binding from a raw MIDI message, as used when a `midi:binding` is given as
+    /// a `midi:MidiEvent` literal. The message type and channel are taken from the status byte. For
+    /// controller messages, the controller number is taken from the first data byte.
+    ///
+    /// # Errors
+    /// Returns an error if the message does not start with a channel voice status byte, or if a
+    /// controller message has no valid controller number.
+    pub fn from_raw_message(message: &[u8]) -> Result<Self, InvalidMidiBindingError> {
+        let invalid = || InvalidMidiBindingError::RawMessage(message.to_vec());
+        let status = *message.first().ok_or_else(invalid)?;
+        let message_type = match status & 0xf0 {
+            0x80 => AtomType::MidiNoteOff,
+            0x90 => AtomType::MidiNoteOn,
+            0xa0 => AtomType::MidiAftertouch,
+            0xb0 => AtomType::MidiController,
+            0xc0 => AtomType::MidiProgramChange,
+            0xd0 => AtomType::MidiChannelPressure,
+            0xe0 => AtomType::MidiBender,
+            _ => return Err(invalid())
+        };
+        let controller_number = if message_type == AtomType::MidiController {
+            Some(*message.get(1).ok_or_else(invalid)?)
+        }
+        else {
+            None
+        };
+
+        MidiBinding::new(message_type, Some(status & 0x0f), controller_number)
+    }
+
+    /// Gets the type of MIDI message. This is always
+    /// [`MidiVoiceMessage`](crate::bundle_model::constants::AtomType::MidiVoiceMessage) or one of
+    /// its subtypes.
+    pub fn message_type(&self) -> AtomType {
+        self.message_type
+    }
+
+    /// Gets the MIDI channel (0 to 15), or [`None`](std::option::Option::None) if the binding
+    /// applies to all channels.
+    pub fn channel(&self) -> Option<u8> {
+        self.channel
+    }
+
+    /// Gets the controller number (0 to 127), or [`None`](std::option::Option::None) if not
+    /// specified.
+    pub fn controller_number(&self) -> Option<u8> {
+        self.controller_number
+    }
+}
+
+/// Checks if a type is a MIDI channel voice message type that can be used in a MIDI binding.
+fn is_voice_message_type(message_type: AtomType) -> bool {
+    matches!(message_type, AtomType::MidiVoiceMessage
+        | AtomType::MidiAftertouch
+        | AtomType::MidiBender
+        | AtomType::MidiChannelPressure
+        | AtomType::MidiController
+        | AtomType::MidiNoteOff
+        | AtomType::MidiNoteOn
+        | AtomType::MidiProgramChange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_controller_message_is_parsed() {
+        let binding = MidiBinding::from_raw_message(&[0xb3, 0x07]).unwrap();
+        assert_eq!(binding.message_type(), AtomType::MidiController);
+        assert_eq!(binding.channel(), Some(3));
+        assert_eq!(binding.controller_number(), Some(7));
+    }
+
+    #[test]
+    fn invalid_bindings_are_rejected() {
+        assert!(MidiBinding::from_raw_message(&[0xf8]).is_err());
+        assert!(MidiBinding::from_raw_message(&[0xb0]).is_err());
+        assert!(MidiBinding::new(AtomType::MidiClock, None, None).is_err());
+        assert!(MidiBinding::new(AtomType::MidiController, Some(16), Some(1)).is_err());
+        assert!(MidiBinding::new(AtomType::MidiController, Some(0), Some(128)).is_err());
+    }
+}
@@ -1,9 +1,11 @@
 //! Defines data structures representing information available in an LV2 bundle's RDF data.
 
 use num_bigint::BigUint;
+#[cfg(feature = "rayon")]
 use rayon::iter::ParallelIterator;
-use crate::rdf_util::Iri;
+use crate::rdf_util::{Iri, Literal, LangTag, lookup_literal};
 use std::borrow::Borrow;
+use std::cmp::Ordering;
 
 pub mod constants;
 pub mod implications;
@@ -11,10 +13,28 @@ pub mod unknowns;
 pub mod symbol;
 pub mod project;
 pub mod port;
+pub mod midi;
+pub mod options;
+pub mod block_length;
+pub mod control;
+pub mod effective;
 pub mod plugin;
 pub mod dyn_manifest;
+pub mod loader;
+pub mod catalog;
+pub mod spec;
+pub mod hierarchy;
 
 pub(crate) mod impl_util;
+#[cfg(test)]
+pub(crate) mod test_util;
+
+/// Error type returned when trying to parse an invalid version number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidVersionError {
+    /// The invalid version number string that we tried to parse.
+    pub attempted_version: String
+}
 
 /// Represents a version specification for an LV2 resource, such as a plugin. A resource version
 /// consists of a minor version number and a micro version number. There is no major version number,
@@ -40,6 +60,22 @@ impl ResourceVersion {
         }
     }
 
+    /// Constructs a resource version object from the literal values of the `lv2:minorVersion` and
+    /// `lv2:microVersion` properties. The literals are expected to contain non-negative integers.
+    ///
+    /// # Parameters
+    /// - `minor_version`: Literal containing the minor version number.
+    /// - `micro_version`: Literal containing the micro version number.
+    ///
+    /// # Errors
+    /// Returns an error if either literal does not contain a non-negative integer.
+    pub fn from_literals(minor_version: &Literal, micro_version: &Literal)
+        -> Result<Self, InvalidVersionError>
+    {
+        Ok(ResourceVersion::new(parse_version_number(minor_version)?,
+            parse_version_number(micro_version)?))
+    }
+
     /// Gets the minor version number.
     pub fn minor_version(&self) -> &BigUint {
         &self.minor_version
@@ -58,10 +94,53 @@ impl ResourceVersion {
     /// Checks if this version represents a development release other than a pre-release.
     pub fn is_dev_release(&self) -> bool {
         // Check if the minor version or micro version is odd.
-        &self.minor_version % &2u8 == 1u8.into() || &self.micro_version % &2u8 == 1u8.into()
+        &self.minor_version % 2u8 == 1u8.into() || &self.micro_version % 2u8 == 1u8.into()
+    }
+
+    /// Checks if this version represents a stable release, i.e. neither a pre-release nor a
+    /// development release.
+    pub fn is_stable_release(&self) -> bool {
+        !self.is_pre_release() && !self.is_dev_release()
+    }
+
+    /// Compares two versions by how strongly a host should prefer to use them when the same
+    /// resource is available in multiple versions. Stable releases are preferred over development
+    /// releases, which are in turn preferred over pre-releases. Among versions of the same kind,
+    /// higher version numbers are preferred. A result of
+    /// [`Greater`](std::cmp::Ordering::Greater) means `self` is preferred over `other`.
+    pub fn cmp_preference(&self, other: &ResourceVersion) -> Ordering {
+        self.release_rank().cmp(&other.release_rank()).then_with(|| self.cmp(other))
+    }
+
+    /// Ranks the kind of release this version represents, with higher values being preferred.
+    fn release_rank(&self) -> u8 {
+        if self.is_pre_release() {
+            0
+        }
+        else if self.is_dev_release() {
+            1
+        }
+        else {
+            2
+        }
+    }
+}
+
+impl Default for ResourceVersion {
+    /// Constructs the version 0.0, which LV2 hosts assume when a resource does not specify a
+    /// version.
+    fn default() -> Self {
+        ResourceVersion::new(0u8.into(), 0u8.into())
     }
 }
 
+/// Parses a version number literal as a non-negative integer.
+pub(crate) fn parse_version_number(literal: &Literal) -> Result<BigUint, InvalidVersionError> {
+    let text = literal.value().trim();
+    let digits = text.strip_prefix('+').unwrap_or(text);
+    digits.parse().map_err(|_| InvalidVersionError { attempted_version: literal.value().to_string() })
+}
+
 /// Trait for types that are required to have an "identifier" of the specified type.
 ///
 /// # Parameters
@@ -98,23 +177,76 @@ pub trait HasRelatedSet<'a, R, T: Sync> {
     /// Type of borrowed element returned by the set iterator.
     type BorrowedElt: Borrow<T> + Send;
 
+    /// Type of sequential iterator returned by
+    /// [`seq_set_iter`](self::HasRelatedSet::seq_set_iter).
+    type SeqSetIter: Iterator<Item = Self::BorrowedElt>;
+
     /// Type of (parallel) iterator returned by [`set_iter`](self::HasRelatedSet::set_iter).
+    #[cfg(feature = "rayon")]
     type SetIter: ParallelIterator<Item = Self::BorrowedElt>;
 
+    /// Gets a sequential iterator over the elements of the set. The returned iterator must not
+    /// repeat elements, and may be empty. Related sets are usually small, so this is typically
+    /// faster than [`set_iter`](self::HasRelatedSet::set_iter) unless a lot of work is done per
+    /// element.
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter;
+
     /// Gets a (parallel) iterator over the elements of the set. The returned iterator must not
-    /// repeat elements, and may be empty.
+    /// repeat elements, and may be empty. Only available with the `rayon` feature.
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter;
 
     /// Checks if the specified element is in the set. The default implementation calls
-    /// [`set_iter`](self::HasRelatedSet::set_iter) and searches for the specified element. A more
-    /// efficient implementation is likely possible for most implementing types.
+    /// [`seq_set_iter`](self::HasRelatedSet::seq_set_iter) and searches for the specified element.
+    /// A more efficient implementation is likely possible for most implementing types.
     fn has_elt(&'a self, to_check: &T) -> bool
         where T: Eq
     {
-        self.set_iter().any(|elt| elt.borrow() == to_check)
+        self.seq_set_iter().any(|elt| elt.borrow() == to_check)
     }
 }
 
+/// Selection of names, labels and documentation according to the user's language preferences. This
+/// trait is implemented for all types, but each method is only available for types that have the
+/// corresponding set of literals. The selection follows
+/// [`lookup_literal`](crate::rdf_util::lookup_literal); the preferred languages can be obtained
+/// from [`preferred_langs_from_env`](crate::rdf_util::preferred_langs_from_env).
+pub trait Localized<'a> {
+    /// Gets the name that best matches the preferred languages, or
+    /// [`None`](std::option::Option::None) if there are no names.
+    fn best_name(&'a self, preferred_langs: &[LangTag]) -> Option<&'a Literal>
+        where Self: HasRelatedSet<'a, NameRelation, Literal, BorrowedElt = &'a Literal>
+    {
+        lookup_literal(self.seq_set_iter(), preferred_langs)
+    }
+
+    /// Gets the short name that best matches the preferred languages, or
+    /// [`None`](std::option::Option::None) if there are no short names.
+    fn best_short_name(&'a self, preferred_langs: &[LangTag]) -> Option<&'a Literal>
+        where Self: HasRelatedSet<'a, ShortNameRelation, Literal, BorrowedElt = &'a Literal>
+    {
+        lookup_literal(self.seq_set_iter(), preferred_langs)
+    }
+
+    /// Gets the label that best matches the preferred languages, or
+    /// [`None`](std::option::Option::None) if there are no labels.
+    fn best_label(&'a self, preferred_langs: &[LangTag]) -> Option<&'a Literal>
+        where Self: HasRelatedSet<'a, LabelRelation, Literal, BorrowedElt = &'a Literal>
+    {
+        lookup_literal(self.seq_set_iter(), preferred_langs)
+    }
+
+    /// Gets the documentation string that best matches the preferred languages, or
+    /// [`None`](std::option::Option::None) if there is no documentation.
+    fn best_doc(&'a self, preferred_langs: &[LangTag]) -> Option<&'a Literal>
+        where Self: HasRelatedSet<'a, DocRelation, Literal, BorrowedElt = &'a Literal>
+    {
+        lookup_literal(self.seq_set_iter(), preferred_langs)
+    }
+}
+
+impl<'a, T: ?Sized> Localized<'a> for T {}
+
 /// Relation type for [`HasRelatedSet`](self::HasRelatedSet), which does not specify the
 /// relationship between the set elements and the instance that "has" the set. This is not meant to
 /// indicate an unknown or undefined relationship; it is simply meant to be used when the
@@ -0,0 +1,157 @@
+//! Representation of LV2 option values, and selection of the options to pass to a plugin or UI.
+
+use std::collections::BTreeSet;
+use enum_map::EnumMap;
+use enumset::EnumSet;
+use ordered_float::OrderedFloat;
+use crate::bundle_model::constants::{AtomType, Lv2Option};
+use crate::bundle_model::unknowns::UnknownOption;
+use crate::bundle_model::impl_util::HostFeatureRequirer;
+
+/// Represents the value of an LV2 option.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OptionValue {
+    /// 32-bit signed integer value (`atom:Int`). Colors are represented as integers containing a
+    /// 32-bit RGBA value.
+    Int(i32),
+
+    /// Single-precision floating point value (`atom:Float`).
+    Float(OrderedFloat<f32>),
+
+    /// UTF-8 string value (`atom:String`).
+    String(String)
+}
+
+impl OptionValue {
+    /// Gets the atom type of the value.
+    pub fn atom_type(&self) -> AtomType {
+        match self {
+            OptionValue::Int(_) => AtomType::Int,
+            OptionValue::Float(_) => AtomType::Float,
+            OptionValue::String(_) => AtomType::String
+        }
+    }
+}
+
+/// Error type returned when trying to use a value of the wrong type for an LV2 option.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidOptionValueError {
+    /// The option for which the value was provided.
+    pub option: Lv2Option,
+
+    /// The value that does not match the option's value type.
+    pub attempted_value: OptionValue
+}
+
+/// Error type returned when a host cannot provide all the LV2 options required by a plugin or UI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MissingOptionsError {
+    /// Required options understood by this crate that the host did not provide.
+    pub missing_options: EnumSet<Lv2Option>,
+
+    /// Required options not understood by this crate. The host cannot provide these through
+    /// [`HostOptions`](self::HostOptions).
+    pub unknown_options: BTreeSet<UnknownOption>
+}
+
+/// Represents the set of LV2 option values that a host is able to provide. Each option has at most
+/// one value, and the value's type always matches the option's
+/// [`value_type`](crate::bundle_model::constants::Lv2Option::value_type).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostOptions {
+    /// Value of each option, if the host provides it.
+    values: EnumMap<Lv2Option, Option<OptionValue>>
+}
+
+impl HostOptions {
+    /// Constructs a set of host options with no values.
+    pub fn new() -> Self {
+        HostOptions {
+            values: EnumMap::from(|_| None)
+        }
+    }
+
+    /// Sets the value of an option, replacing any previous value.
+    ///
+    /// # Errors
+    /// Returns an error if the value's type does not match the option's value type.
+    pub fn set(&mut self, option: Lv2Option, value: OptionValue) -> Result<(), InvalidOptionValueError> {
+        if value.atom_type() != option.value_type() {
+            return Err(InvalidOptionValueError { option, attempted_value: value });
+        }
+        self.values[option] = Some(value);
+        Ok(())
+    }
+
+    /// Gets the value of an option, or [`None`](std::option::Option::None) if the host does not
+    /// provide the option.
+    pub fn get(&self, option: Lv2Option) -> Option<&OptionValue> {
+        self.values[option].as_ref()
+    }
+}
+
+impl Default for HostOptions {
+    fn default() -> Self {
+        HostOptions::new()
+    }
+}
+
+impl HostFeatureRequirer {
+    /// Selects the option values to pass to the entity, given the options available from the host.
+    /// All required options and all optionally supported options that the host provides are
+    /// included, in the order of the [`Lv2Option`](crate::bundle_model::constants::Lv2Option)
+    /// enum.
+    pub(crate) fn select_options(&self, host_options: &HostOptions)
+        -> Result<Vec<(Lv2Option, OptionValue)>, MissingOptionsError>
+    {
+        let required = self.required_options.knowns();
+        let missing_options: EnumSet<Lv2Option> = required.iter()
+            .filter(|option| host_options.get(*option).is_none())
+            .collect();
+        let unknown_options: BTreeSet<UnknownOption> = self.required_options.unknowns().clone();
+        if !missing_options.is_empty() || !unknown_options.is_empty() {
+            return Err(MissingOptionsError { missing_options, unknown_options });
+        }
+
+        Ok(required.union(self.optional_options.knowns()).iter()
+            .filter_map(|option| host_options.get(option).map(|value| (option, value.clone())))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdf_util::Graph;
+    use crate::bundle_model::constants::IriConstant;
+    use crate::bundle_model::loader::{load_plugin, OPTS_REQUIRED_OPTION, OPTS_SUPPORTED_OPTION};
+    use crate::bundle_model::test_util::{iri, iri_node, add_plugin};
+
+    #[test]
+    fn options_are_selected_from_declared_needs() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        graph.insert(plugin.clone(), iri(OPTS_REQUIRED_OPTION), iri_node(Lv2Option::MaxBlockLength.iri()));
+        graph.insert(plugin, iri(OPTS_SUPPORTED_OPTION), iri_node(Lv2Option::SampleRate.iri()));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        let mut host_options = HostOptions::new();
+        assert_eq!(plugin.select_options(&host_options).unwrap_err().missing_options,
+            EnumSet::only(Lv2Option::MaxBlockLength));
+
+        host_options.set(Lv2Option::MaxBlockLength, OptionValue::Int(4096)).unwrap();
+        host_options.set(Lv2Option::SampleRate, OptionValue::Float(OrderedFloat(48000.0))).unwrap();
+        host_options.set(Lv2Option::ScaleFactor, OptionValue::Float(OrderedFloat(2.0))).unwrap();
+        assert_eq!(plugin.select_options(&host_options).unwrap(), vec![
+            (Lv2Option::MaxBlockLength, OptionValue::Int(4096)),
+            (Lv2Option::SampleRate, OptionValue::Float(OrderedFloat(48000.0)))
+        ]);
+    }
+
+    #[test]
+    fn mistyped_option_values_are_rejected() {
+        let mut host_options = HostOptions::new();
+        assert!(host_options.set(Lv2Option::SampleRate, OptionValue::Int(48000)).is_err());
+        assert!(host_options.get(Lv2Option::SampleRate).is_none());
+    }
+}
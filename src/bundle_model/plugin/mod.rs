@@ -1,76 +1,124 @@
 //! Representation of LV2 plugins.
 
+use std::collections::btree_set;
+#[cfg(feature = "rayon")]
 use std::collections::BTreeSet;
 use crate::rdf_util::{Literal, Iri};
-use enumset::EnumSetIter;
-use crate::bundle_model::constants::{ExtensionData, HostFeature, PluginType, Lv2Option};
+use enumset::{EnumSet, EnumSetIter};
+use crate::bundle_model::constants::{ExtensionData, HostFeature, PluginType, Lv2Option, PortDesignation, PortProperty, PortType, TimeDesignation};
 use crate::bundle_model::unknowns::{UnknownHostFeature, UnknownExtensionData, UnknownOption, UnknownPluginType};
-use num_bigint::BigUint;
+#[cfg(feature = "rayon")]
 use rayon::iter::{IterBridge, IntoParallelRefIterator, ParallelIterator};
 use crate::bundle_model::{ResourceVersion, Loadable, IdentifiedBy, OptionallyIdentifiedBy, HasRelatedSet, NameRelation, ShortNameRelation, DocRelation, RequiresRelation, OptionallySupportsRelation, ProvidesRelation};
 use crate::bundle_model::symbol::Symbol;
 use crate::bundle_model::project::ProjectInfo;
 use crate::bundle_model::port::PortInfo;
+use crate::bundle_model::midi::MidiBinding;
+use crate::bundle_model::options::{HostOptions, OptionValue, MissingOptionsError};
+use crate::bundle_model::block_length::BlockLengthContract;
 use crate::bundle_model::impl_util::{KnownAndUnknownSet, NamedImpl, DocumentedImpl, HostFeatureRequirer};
+use crate::bundle_model::hierarchy::{PluginTypeHierarchy, known_iri};
+use crate::bundle_model::implications::{PLUGIN_TYPES_IMPLIED, HOST_FEATURES_IMPLIED, host_features_required_by_extension_data};
 
 /// Representation of an LV2 plugin.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PluginInfo {
     /// IRI identifying the plugin.
-    iri: Iri,
+    pub(crate) iri: Iri,
 
     /// LV2 symbol identifying the plugin.
-    symbol: Option<Symbol>,
+    pub(crate) symbol: Option<Symbol>,
 
     /// URI pointing to the shared library that implements the plugin.
-    binary: Iri,
+    pub(crate) binary: Iri,
 
     /// Set of LV2 plugin types to which the plugin belongs.
-    plugin_types: KnownAndUnknownSet<PluginType, UnknownPluginType>,
+    pub(crate) plugin_types: KnownAndUnknownSet<PluginType, UnknownPluginType>,
 
     /// Plugin version.
-    version: ResourceVersion,
+    pub(crate) version: ResourceVersion,
 
     /// Name and short name information.
-    named_impl: NamedImpl,
+    pub(crate) named_impl: NamedImpl,
 
     /// Documentation information.
-    documented_impl: DocumentedImpl,
+    pub(crate) documented_impl: DocumentedImpl,
 
     // TODO: Avoid creating multiple ProjectInfo objects for the same project if multiple plugins
     // are part of the same project. Maybe use Option<Iri> instead of Option<ProjectInfo>?
     /// Description of the project to which the plugin belongs, if specified.
-    project: Option<ProjectInfo>,
+    pub(crate) project: Option<ProjectInfo>,
 
     /// Description of the plugin's ports, in order of their port indices.
-    ports: Vec<PortInfo>,
+    pub(crate) ports: Vec<PortInfo>,
 
     /// Set of LV2 extension data interfaces provided by the plugin.
-    provided_extension_data: KnownAndUnknownSet<ExtensionData, UnknownExtensionData>,
+    pub(crate) provided_extension_data: KnownAndUnknownSet<ExtensionData, UnknownExtensionData>,
 
     /// Information about required (and optional) host features and LV2 options.
-    host_feature_requirer: HostFeatureRequirer,
-
-    /// Number of latency frames introduced by the plugin, if specified.
-    latency: Option<BigUint>,
-
-    /// Flag indicating whether the plugin is enabled or bypassed. Most bundles probably won't
-    /// specify this, as its value seems to only make sense at runtime.
-    enabled: Option<bool>,
-
-    /// Flag indicating whether the plugin is processing as fast as possible (true) or being limited
-    /// to real time (false). Most bundles probably won't specify this, as its value seems to only
-    /// make sense at runtime.
-    free_wheeling: Option<bool>
+    pub(crate) host_feature_requirer: HostFeatureRequirer
 }
 
 impl PluginInfo {
+    /// Gets a sequential iterator over the known plugin types to which the plugin belongs.
+    pub fn known_plugin_types_seq_iter(&self) -> impl Iterator<Item = PluginType> {
+        self.plugin_types.knowns_seq_iter()
+    }
+
     /// Gets a (parallel) iterator over the known plugin types to which the plugin belongs.
+    #[cfg(feature = "rayon")]
     pub fn known_plugin_types_iter(&self) -> impl ParallelIterator<Item = PluginType> {
         self.plugin_types.knowns_iter()
     }
 
+    /// Gets the known plugin types to which the plugin belongs, including the types implied by
+    /// [`PLUGIN_TYPES_IMPLIED`](crate::bundle_model::implications::PLUGIN_TYPES_IMPLIED). For
+    /// example, a plugin stated to be a compressor is also reported as a dynamics plugin.
+    pub fn all_plugin_types(&self) -> EnumSet<PluginType> {
+        PLUGIN_TYPES_IMPLIED.reachable_nodes_from_multi(self.plugin_types.knowns())
+    }
+
+    /// Gets the most specific known plugin types to which the plugin belongs, i.e. the types from
+    /// [`all_plugin_types`](self::PluginInfo::all_plugin_types) that are not implied by any other
+    /// type in that set. For example, a plugin stated to be both a compressor and a dynamics
+    /// plugin is only reported as a compressor.
+    pub fn most_specific_types(&self) -> EnumSet<PluginType> {
+        let all_types = self.all_plugin_types();
+        all_types.iter()
+            .filter(|plugin_type| {
+                !(all_types - *plugin_type).iter()
+                    .any(|other| PLUGIN_TYPES_IMPLIED.has_edge(other, *plugin_type))
+            })
+            .collect()
+    }
+
+    /// Checks if the plugin belongs to a known plugin type, either directly or through the
+    /// hierarchy of plugin types (e.g. because one of its unknown types is a vendor subclass of
+    /// the type).
+    ///
+    /// # Parameters
+    /// - `plugin_type`: The plugin type to check.
+    /// - `hierarchy`: The plugin type hierarchy to use.
+    pub fn is_of_type(&self, plugin_type: PluginType, hierarchy: &PluginTypeHierarchy) -> bool {
+        self.stated_plugin_type_iris()
+            .any(|iri| hierarchy.known_supertypes(&iri).contains(plugin_type))
+    }
+
+    /// Gets an iterator over the IRIs of the (known and unknown) plugin types to which the plugin
+    /// is stated to belong, not including implied types.
+    pub(crate) fn stated_plugin_type_iris(&self) -> impl Iterator<Item = Iri> + '_ {
+        self.plugin_types.knowns_seq_iter()
+            .map(known_iri)
+            .chain(self.plugin_types.unknowns_seq_iter().map(|unknown| unknown.id().clone()))
+    }
+
+    /// Gets a sequential iterator over the unknown plugin types to which the plugin belongs.
+    pub fn unknown_plugin_types_seq_iter(&self) -> impl Iterator<Item = &UnknownPluginType> {
+        self.plugin_types.unknowns_seq_iter()
+    }
+
     /// Gets a (parallel) iterator over the unknown plugin types to which the plugin belongs.
+    #[cfg(feature = "rayon")]
     pub fn unknown_plugin_types_iter(&self) -> impl ParallelIterator<Item = &UnknownPluginType> {
         self.plugin_types.unknowns_iter()
     }
@@ -86,27 +134,134 @@ impl PluginInfo {
         self.project.as_ref()
     }
 
-    /// Gets the number of latency frames introduced by the plugin. Returns
-    /// [`None`](std::option::Option::None) if the bundle does not specify a latency amount for the
-    /// plugin.
-    pub fn latency(&self) -> Option<&BigUint> {
-        self.latency.as_ref()
+    /// Gets the plugin's ports, in order of their port indices.
+    pub fn ports(&self) -> &[PortInfo] {
+        &self.ports
+    }
+
+    /// Gets the first port (in index order) that has the specified designation, or
+    /// [`None`](std::option::Option::None) if no port has the designation.
+    pub fn designated_port(&self, designation: PortDesignation) -> Option<&PortInfo> {
+        self.ports.iter().find(|port| port.designations.contains(designation))
+    }
+
+    /// Gets the input port that the host should use to enable or bypass the plugin, i.e. the port
+    /// designated `lv2:enabled`. Returns [`None`](std::option::Option::None) if the plugin does not
+    /// have an enable port, in which case the host must implement bypassing itself.
+    pub fn enable_port(&self) -> Option<&PortInfo> {
+        self.designated_input_port(PortDesignation::Enabled)
+    }
+
+    /// Gets the input port that the host should use to tell the plugin whether it is
+    /// free-wheeling, i.e. the port designated `lv2:freeWheeling`. Returns
+    /// [`None`](std::option::Option::None) if the plugin does not have such a port.
+    pub fn free_wheeling_port(&self) -> Option<&PortInfo> {
+        self.designated_input_port(PortDesignation::FreeWheeling)
     }
 
-    /// Gets a boolean indicating whether the plugin is enabled (true) or bypassed (false). Returns
-    /// [`None`](std::option::Option::None) if the bundle does not specify an enabled/bypassed
-    /// state. Most bundles probably won't specify this flag, as its value seems to only make sense
-    /// at runtime.
-    pub fn enabled(&self) -> Option<bool> {
-        self.enabled
+    /// Gets the output port through which the plugin reports its latency, in audio frames. A port
+    /// designated `lv2:latency` is preferred, but an output port with the older `lv2:reportsLatency`
+    /// property is also accepted. Returns [`None`](std::option::Option::None) if the plugin does
+    /// not report its latency.
+    pub fn latency_port(&self) -> Option<&PortInfo> {
+        self.designated_output_port(PortDesignation::Latency)
+            .or_else(|| self.ports.iter().find(|port| {
+                port.is_output() && port.port_props.contains_known(PortProperty::ReportsLatency)
+            }))
     }
 
-    /// Gets a boolean indicating whether the plugin is running as fast as possible (true) or being
-    /// limited to real time (false). Returns [`None`](std::option::Option::None) if the bundle does
-    /// not specify a free-wheeling state. Most bundles probably won't specify this flag, as its
-    /// value seems to only make sense at runtime.
-    pub fn free_wheeling(&self) -> Option<bool> {
-        self.free_wheeling
+    /// Gets the input port designated as the plugin's main control channel (`lv2:control`), e.g.
+    /// the port that receives MIDI events for an instrument plugin. Returns
+    /// [`None`](std::option::Option::None) if no input port has this designation.
+    pub fn control_input_port(&self) -> Option<&PortInfo> {
+        self.designated_input_port(PortDesignation::Control)
+    }
+
+    /// Gets the input port that receives the specified property of the transport position, or
+    /// [`None`](std::option::Option::None) if no input port has the specified time designation.
+    pub fn time_designated_port(&self, designation: TimeDesignation) -> Option<&PortInfo> {
+        self.ports.iter().find(|port| port.is_input() && port.time_designations.contains(designation))
+    }
+
+    /// Gets an iterator over the input atom ports through which the plugin accepts `time:Position`
+    /// objects, in index order.
+    pub fn time_position_ports(&self) -> impl Iterator<Item = &PortInfo> {
+        self.ports.iter().filter(|port| port.is_input() && port.supports_time_position)
+    }
+
+    /// Checks if the plugin wants to receive transport information from the host, either as
+    /// `time:Position` objects or through time-designated input ports. Hosts can use this to avoid
+    /// sending transport information to plugins that do not care about it.
+    pub fn wants_transport(&self) -> bool {
+        self.time_position_ports().next().is_some()
+            || self.ports.iter().any(|port| port.is_input() && !port.time_designations.is_empty())
+    }
+
+    /// Gets an iterator over the input ports that have a suggested MIDI binding, along with their
+    /// bindings, in index order. Hosts can use this to apply default MIDI controller mappings.
+    pub fn midi_bindings(&self) -> impl Iterator<Item = (&PortInfo, &MidiBinding)> {
+        self.ports.iter()
+            .filter(|port| port.is_input())
+            .filter_map(|port| port.midi_binding.as_ref().map(|binding| (port, binding)))
+    }
+
+    /// Selects the LV2 option values to pass to the plugin when instantiating it, given the options
+    /// available from the host. The result contains every required option (`opts:requiredOption`)
+    /// and every optionally supported option (`opts:supportedOption`) that the host provides, and
+    /// can be used to build the `LV2_Options_Option` array.
+    ///
+    /// # Errors
+    /// Returns an error listing the required options that the host cannot provide, if any.
+    pub fn select_options(&self, host_options: &HostOptions)
+        -> Result<Vec<(Lv2Option, OptionValue)>, MissingOptionsError>
+    {
+        self.host_feature_requirer.select_options(host_options)
+    }
+
+    /// Selects the concrete data type that the host should use for each port, given the data types
+    /// the host is able to connect. The result is in port index order, and contains
+    /// [`None`](std::option::Option::None) for ports that the host cannot connect. See
+    /// [`PortInfo::resolve_type`](crate::bundle_model::port::PortInfo::resolve_type) for details.
+    pub fn resolve_port_types(&self, connectable: EnumSet<PortType>) -> Vec<Option<PortType>> {
+        self.ports.iter().map(|port| port.resolve_type(connectable)).collect()
+    }
+
+    /// Computes the block length contract that a host must honour when running the plugin, based
+    /// on the buf-size host features and options the plugin requires. Hosts can check a proposed
+    /// block configuration against the contract to decide whether the plugin can be run directly
+    /// or needs a re-blocking adapter.
+    pub fn block_length_contract(&self) -> BlockLengthContract {
+        self.host_feature_requirer.block_length_contract()
+    }
+
+    /// Gets all known host features that the plugin effectively requires. In addition to the
+    /// features the plugin requires explicitly, this includes the features they imply (see
+    /// [`HOST_FEATURES_IMPLIED`](crate::bundle_model::implications::HOST_FEATURES_IMPLIED)) and the
+    /// features needed by the extension data interfaces the plugin provides (see
+    /// [`host_features_required_by_extension_data`](crate::bundle_model::implications::host_features_required_by_extension_data)).
+    pub fn all_required_host_features(&self) -> EnumSet<HostFeature> {
+        let by_extension_data = self.provided_extension_data.knowns().iter()
+            .fold(EnumSet::empty(), |features, extension_data| {
+                features | host_features_required_by_extension_data(extension_data)
+            });
+        self.host_feature_requirer.all_required_host_features()
+            | HOST_FEATURES_IMPLIED.reachable_nodes_from_multi(by_extension_data)
+    }
+
+    /// Checks if the plugin effectively requires a host feature, taking implications into
+    /// account (see [`all_required_host_features`](self::PluginInfo::all_required_host_features)).
+    pub fn requires_host_feature(&self, host_feature: HostFeature) -> bool {
+        self.all_required_host_features().contains(host_feature)
+    }
+
+    /// Gets the first input port (in index order) that has the specified designation.
+    fn designated_input_port(&self, designation: PortDesignation) -> Option<&PortInfo> {
+        self.ports.iter().find(|port| port.is_input() && port.designations.contains(designation))
+    }
+
+    /// Gets the first output port (in index order) that has the specified designation.
+    fn designated_output_port(&self, designation: PortDesignation) -> Option<&PortInfo> {
+        self.ports.iter().find(|port| port.is_output() && port.designations.contains(designation))
     }
 }
 
@@ -130,8 +285,15 @@ impl Loadable for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, NameRelation, Literal> for PluginInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.named_impl.names.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.named_impl.names.par_iter()
     }
@@ -139,8 +301,15 @@ impl<'a> HasRelatedSet<'a, NameRelation, Literal> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, ShortNameRelation, Literal> for PluginInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.named_impl.short_names.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.named_impl.short_names.par_iter()
     }
@@ -148,8 +317,15 @@ impl<'a> HasRelatedSet<'a, ShortNameRelation, Literal> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, DocRelation, Literal> for PluginInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.documented_impl.documentation.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.documented_impl.documentation.par_iter()
     }
@@ -157,8 +333,15 @@ impl<'a> HasRelatedSet<'a, DocRelation, Literal> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, ProvidesRelation, ExtensionData> for PluginInfo {
     type BorrowedElt = ExtensionData;
+    type SeqSetIter = EnumSetIter<ExtensionData>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<ExtensionData>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.provided_extension_data.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.provided_extension_data.knowns_iter()
     }
@@ -166,8 +349,15 @@ impl<'a> HasRelatedSet<'a, ProvidesRelation, ExtensionData> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, ProvidesRelation, UnknownExtensionData> for PluginInfo {
     type BorrowedElt = &'a UnknownExtensionData;
+    type SeqSetIter = btree_set::Iter<'a, UnknownExtensionData>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownExtensionData> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.provided_extension_data.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.provided_extension_data.unknowns_iter()
     }
@@ -175,8 +365,15 @@ impl<'a> HasRelatedSet<'a, ProvidesRelation, UnknownExtensionData> for PluginInf
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, HostFeature> for PluginInfo {
     type BorrowedElt = HostFeature;
+    type SeqSetIter = EnumSetIter<HostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<HostFeature>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_host_features.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_host_features.knowns_iter()
     }
@@ -184,8 +381,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, HostFeature> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownHostFeature> for PluginInfo {
     type BorrowedElt = &'a UnknownHostFeature;
+    type SeqSetIter = btree_set::Iter<'a, UnknownHostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownHostFeature> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_host_features.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_host_features.unknowns_iter()
     }
@@ -193,8 +397,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownHostFeature> for PluginInfo
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, Lv2Option> for PluginInfo {
     type BorrowedElt = Lv2Option;
+    type SeqSetIter = EnumSetIter<Lv2Option>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<Lv2Option>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_options.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_options.knowns_iter()
     }
@@ -202,8 +413,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, Lv2Option> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownOption> for PluginInfo {
     type BorrowedElt = &'a UnknownOption;
+    type SeqSetIter = btree_set::Iter<'a, UnknownOption>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownOption> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.required_options.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.required_options.unknowns_iter()
     }
@@ -211,8 +429,15 @@ impl<'a> HasRelatedSet<'a, RequiresRelation, UnknownOption> for PluginInfo {
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, HostFeature> for PluginInfo {
     type BorrowedElt = HostFeature;
+    type SeqSetIter = EnumSetIter<HostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<HostFeature>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_host_features.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_host_features.knowns_iter()
     }
@@ -220,8 +445,15 @@ impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, HostFeature> for PluginIn
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, UnknownHostFeature> for PluginInfo {
     type BorrowedElt = &'a UnknownHostFeature;
+    type SeqSetIter = btree_set::Iter<'a, UnknownHostFeature>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownHostFeature> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_host_features.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_host_features.unknowns_iter()
     }
@@ -229,8 +461,15 @@ impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, UnknownHostFeature> for P
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, Lv2Option> for PluginInfo {
     type BorrowedElt = Lv2Option;
+    type SeqSetIter = EnumSetIter<Lv2Option>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<Lv2Option>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_options.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_options.knowns_iter()
     }
@@ -238,9 +477,144 @@ impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, Lv2Option> for PluginInfo
 
 impl<'a> HasRelatedSet<'a, OptionallySupportsRelation, UnknownOption> for PluginInfo {
     type BorrowedElt = &'a UnknownOption;
+    type SeqSetIter = btree_set::Iter<'a, UnknownOption>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownOption> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.host_feature_requirer.optional_options.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.host_feature_requirer.optional_options.unknowns_iter()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rdf_util::{Graph, Node, Literal, LangTag};
+    use crate::bundle_model::constants::{IriConstant, PluginType, PortType, PortDesignation, PortProperty, TimeDesignation, HostFeature, ExtensionData, Lv2Option};
+    use crate::bundle_model::unknowns::{UnknownHostFeature, UnknownOption};
+    use crate::bundle_model::spec::SpecCatalog;
+    use crate::bundle_model::hierarchy::PluginTypeHierarchy;
+    use crate::bundle_model::loader::{load_plugin, RDF_TYPE, RDFS_SUBCLASS_OF, LV2_SPECIFICATION, DOAP_NAME, LV2_DESIGNATION, LV2_PORT_PROPERTY, ATOM_SUPPORTS, TIME_POSITION, LV2_REQUIRED_FEATURE, LV2_OPTIONAL_FEATURE, OPTS_REQUIRED_OPTION, OPTS_SUPPORTED_OPTION, LV2_EXTENSION_DATA};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node, add_plugin, add_port, related_set};
+    use crate::bundle_model::{IdentifiedBy, NameRelation, ShortNameRelation, RequiresRelation, OptionallySupportsRelation, ProvidesRelation};
+
+    #[test]
+    fn implied_plugin_types_are_applied() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        for plugin_type in &[PluginType::ParaEQ, PluginType::Dynamics, PluginType::Compressor] {
+            graph.insert(plugin.clone(), iri(RDF_TYPE), iri_node(plugin_type.iri()));
+        }
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        assert_eq!(plugin.all_plugin_types(), PluginType::ParaEQ | PluginType::EQ | PluginType::Filter
+            | PluginType::Compressor | PluginType::Dynamics);
+        assert_eq!(plugin.most_specific_types(), PluginType::ParaEQ | PluginType::Compressor);
+    }
+
+    #[test]
+    fn vendor_plugin_types_are_resolved_through_hierarchy() {
+        let mut graph = Graph::new();
+        graph.insert(iri_node("http://example.org/ext"), iri(RDF_TYPE), iri_node(LV2_SPECIFICATION));
+        graph.insert(iri_node("http://example.org/ext#Ladder"), iri(RDFS_SUBCLASS_OF),
+            iri_node(PluginType::Lowpass.iri()));
+        let mut catalog = SpecCatalog::new();
+        catalog.add_bundle(&graph).unwrap();
+        let hierarchy = PluginTypeHierarchy::with_spec_catalog(&catalog);
+
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        graph.insert(plugin, iri(RDF_TYPE), iri_node("http://example.org/ext#Ladder"));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        assert!(plugin.is_of_type(PluginType::Filter, &hierarchy));
+        assert!(!plugin.is_of_type(PluginType::Filter, &PluginTypeHierarchy::new()));
+        assert!(!plugin.is_of_type(PluginType::Reverb, &hierarchy));
+    }
+
+    #[test]
+    fn implied_host_features_are_required() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        graph.insert(plugin.clone(), iri(LV2_REQUIRED_FEATURE), iri_node(HostFeature::FixedBlockLength.iri()));
+        graph.insert(plugin, iri(LV2_EXTENSION_DATA), iri_node(ExtensionData::Worker.iri()));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        assert_eq!(plugin.all_required_host_features(), HostFeature::FixedBlockLength
+            | HostFeature::BoundedBlockLength | HostFeature::Options | HostFeature::WorkSchedule);
+        assert!(plugin.requires_host_feature(HostFeature::WorkSchedule));
+        assert!(!plugin.requires_host_feature(HostFeature::PowerOf2BlockLength));
+    }
+
+    #[test]
+    fn runtime_designations_are_found_on_ports() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let input = [PortType::InputPort.iri(), PortType::Control.iri()];
+        let output = [PortType::OutputPort.iri(), PortType::Control.iri()];
+        add_port(&mut graph, &plugin, 0, "gain", &input);
+        let enable = add_port(&mut graph, &plugin, 1, "enable", &input);
+        graph.insert(enable, iri(LV2_DESIGNATION), iri_node(PortDesignation::Enabled.iri()));
+        let latency = add_port(&mut graph, &plugin, 2, "latency", &output);
+        graph.insert(latency, iri(LV2_PORT_PROPERTY), iri_node(PortProperty::ReportsLatency.iri()));
+
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        assert_eq!(plugin.enable_port().map(|port| *port.id()), Some(1));
+        assert_eq!(plugin.latency_port().map(|port| *port.id()), Some(2));
+        assert!(plugin.free_wheeling_port().is_none());
+        assert!(plugin.control_input_port().is_none());
+    }
+
+    #[test]
+    fn transport_ports_are_reported() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let control_in = [PortType::InputPort.iri(), PortType::Control.iri()];
+        let atom_in = [PortType::InputPort.iri(), PortType::Atom.iri()];
+        add_port(&mut graph, &plugin, 0, "gain", &control_in);
+        let bpm = add_port(&mut graph, &plugin, 1, "bpm", &control_in);
+        graph.insert(bpm, iri(LV2_DESIGNATION), iri_node(TimeDesignation::BeatsPerMinute.iri()));
+        let events = add_port(&mut graph, &plugin, 2, "events", &atom_in);
+        graph.insert(events, iri(ATOM_SUPPORTS), iri_node(TIME_POSITION));
+
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        assert!(plugin.wants_transport());
+        assert_eq!(plugin.time_designated_port(TimeDesignation::BeatsPerMinute).map(|port| *port.id()),
+            Some(1));
+        assert!(plugin.time_designated_port(TimeDesignation::Speed).is_none());
+        let position_ports: Vec<_> = plugin.time_position_ports().map(|port| *port.id()).collect();
+        assert_eq!(position_ports, vec![2]);
+    }
+
+    #[test]
+    fn sequential_and_parallel_related_sets_match() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        graph.insert(plugin.clone(), iri(DOAP_NAME), literal_node("Plugin"));
+        graph.insert(plugin.clone(), iri(DOAP_NAME), Node::Literal(Literal::with_lang_tag(String::from("Greffon"),
+            LangTag::new(String::from("fr")).unwrap())));
+        for feature in &[HostFeature::UridMap.iri(), HostFeature::Options.iri(), "urn:test:feature"] {
+            graph.insert(plugin.clone(), iri(LV2_REQUIRED_FEATURE), iri_node(feature));
+        }
+        graph.insert(plugin.clone(), iri(LV2_OPTIONAL_FEATURE), iri_node(HostFeature::HardRtCapable.iri()));
+        graph.insert(plugin.clone(), iri(OPTS_REQUIRED_OPTION), iri_node(Lv2Option::MaxBlockLength.iri()));
+        graph.insert(plugin.clone(), iri(OPTS_SUPPORTED_OPTION), iri_node("urn:test:option"));
+        graph.insert(plugin, iri(LV2_EXTENSION_DATA), iri_node(ExtensionData::Worker.iri()));
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        assert_eq!(related_set::<NameRelation, Literal, _>(&plugin).len(), 2);
+        assert_eq!(related_set::<RequiresRelation, HostFeature, _>(&plugin),
+            vec![HostFeature::UridMap, HostFeature::Options].into_iter().collect());
+        assert_eq!(related_set::<RequiresRelation, UnknownHostFeature, _>(&plugin).len(), 1);
+        assert_eq!(related_set::<OptionallySupportsRelation, HostFeature, _>(&plugin),
+            vec![HostFeature::HardRtCapable].into_iter().collect());
+        assert_eq!(related_set::<RequiresRelation, Lv2Option, _>(&plugin),
+            vec![Lv2Option::MaxBlockLength].into_iter().collect());
+        assert_eq!(related_set::<OptionallySupportsRelation, UnknownOption, _>(&plugin).len(), 1);
+        assert_eq!(related_set::<ProvidesRelation, ExtensionData, _>(&plugin),
+            vec![ExtensionData::Worker].into_iter().collect());
+        assert!(related_set::<ShortNameRelation, Literal, _>(&plugin).is_empty());
+    }
+}
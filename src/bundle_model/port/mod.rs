@@ -1,14 +1,19 @@
 //! Representation of LV2 ports.
 
+#[cfg(feature = "rayon")]
 use rayon::iter::{IntoParallelRefIterator, IterBridge, ParallelBridge};
 use crate::bundle_model::{HasRelatedSet, NameRelation, ShortNameRelation, DocRelation, TypeRelation, LabelRelation, GenericRelation, IdentifiedBy, OptionallyIdentifiedBy};
 use crate::bundle_model::impl_util::{KnownAndUnknownSet, DocumentedImpl, NamedImpl};
-use crate::bundle_model::constants::{PortType, PortDesignation, PortChannel, PortProperty};
+use crate::bundle_model::constants::{PortType, PortDesignation, PortChannel, PortProperty, TimeDesignation, Unit};
 use crate::bundle_model::unknowns::{UnknownPortType, UnknownPortDesignation, UnknownPortProperty};
 use crate::bundle_model::symbol::Symbol;
-use crate::rdf_util::Literal;
+use crate::bundle_model::midi::MidiBinding;
+use crate::bundle_model::control::ControlRange;
+use crate::bundle_model::effective::EffectivePort;
+use crate::bundle_model::implications::PORT_PROPERTIES_IMPLIED;
+use crate::rdf_util::{Literal, LangTag, lookup_literal};
 use enumset::{EnumSet, EnumSetIter};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, btree_set};
 use ordered_float::OrderedFloat;
 use num_bigint::BigUint;
 
@@ -16,16 +21,53 @@ use num_bigint::BigUint;
 #[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct ScalePoint {
     /// Labels to be displayed in association with the scale point.
-    labels: BTreeSet<Literal>,
+    pub(crate) labels: BTreeSet<Literal>,
 
     /// Control port value for the scale point.
-    value: OrderedFloat<f32>
+    pub(crate) value: OrderedFloat<f32>
+}
+
+impl ScalePoint {
+    /// Constructs a scale point.
+    ///
+    /// # Parameters
+    /// - `value`: Control port value for the scale point.
+    /// - `labels`: Labels to be displayed in association with the scale point, typically in
+    ///   several languages.
+    pub fn new(value: f32, labels: BTreeSet<Literal>) -> Self {
+        ScalePoint {
+            labels,
+            value: OrderedFloat(value)
+        }
+    }
+
+    /// Gets the control port value for the scale point.
+    pub fn value(&self) -> f32 {
+        self.value.into_inner()
+    }
+
+    /// Gets the label to display for the scale point, given the user's preferred languages. This
+    /// is equivalent to [`Localized::best_label`](crate::bundle_model::Localized::best_label).
+    /// Returns [`None`](std::option::Option::None) only if the scale point has no labels.
+    ///
+    /// # Parameters
+    /// - `preferred_langs`: Language tags in order of decreasing preference.
+    pub fn label(&self, preferred_langs: &[LangTag]) -> Option<&Literal> {
+        lookup_literal(&self.labels, preferred_langs)
+    }
 }
 
 impl<'a> HasRelatedSet<'a, LabelRelation, Literal> for ScalePoint {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.labels.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.labels.par_iter()
     }
@@ -35,60 +77,237 @@ impl<'a> HasRelatedSet<'a, LabelRelation, Literal> for ScalePoint {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PortInfo {
     /// Set of LV2 port types to which the port belongs.
-    port_types: KnownAndUnknownSet<PortType, UnknownPortType>,
+    pub(crate) port_types: KnownAndUnknownSet<PortType, UnknownPortType>,
 
     // TODO: Make a separate type for the port index so the IdentifiedBy impl is clearer?
 
     /// Port index.
-    index: u32,
+    pub(crate) index: u32,
 
     /// Port symbol.
-    symbol: Option<Symbol>,
+    pub(crate) symbol: Option<Symbol>,
 
     /// Name and short name information.
-    named_impl: NamedImpl,
+    pub(crate) named_impl: NamedImpl,
 
     /// Documentation information.
-    documented_impl: DocumentedImpl,
+    pub(crate) documented_impl: DocumentedImpl,
 
     /// Default value for the port.
-    default_value: Option<Literal>,
+    pub(crate) default_value: Option<Literal>,
 
     /// Soft maximum value for the port. Hosts *are* allowed to set the value higher.
-    max_value: Option<Literal>,
+    pub(crate) max_value: Option<Literal>,
 
     /// Soft minimum value for the port. Hosts *are* allowed to set the value lower.
-    min_value: Option<Literal>,
+    pub(crate) min_value: Option<Literal>,
 
     /// Scale points for the port, i.e. special marked values.
-    scale_pts: BTreeSet<ScalePoint>,
+    pub(crate) scale_pts: BTreeSet<ScalePoint>,
 
     /// LV2 port properties that apply to the port.
-    port_props: KnownAndUnknownSet<PortProperty, UnknownPortProperty>,
+    pub(crate) port_props: KnownAndUnknownSet<PortProperty, UnknownPortProperty>,
 
     /// Standard LV2 designations that apply to the port.
-    designations: EnumSet<PortDesignation>,
+    pub(crate) designations: EnumSet<PortDesignation>,
 
     /// Standard LV2 channel designations that apply to the port.
-    channel_designations: EnumSet<PortChannel>,
+    pub(crate) channel_designations: EnumSet<PortChannel>,
+
+    /// Standard LV2 time designations that apply to the port.
+    pub(crate) time_designations: EnumSet<TimeDesignation>,
 
     /// Unknown LV2 designations (including channel designations) that apply to the port.
-    unknown_designations: BTreeSet<UnknownPortDesignation>,
+    pub(crate) unknown_designations: BTreeSet<UnknownPortDesignation>,
 
     /// Indicates how 'important' it is to display the port, for scenarios where not all ports can
     /// be displayed. A higher value means higher priority.
-    display_priority: Option<BigUint>,
+    pub(crate) display_priority: Option<BigUint>,
 
     /// Indicates into how many evenly spaced steps the port's range should be divided, when using a
     /// step-based controller such as arrow keys.
-    range_steps: Option<BigUint>,
+    pub(crate) range_steps: Option<BigUint>,
 
     /// Minimum allowed buffer size for the port, in bytes.
-    min_buffer_size: Option<BigUint>,
+    pub(crate) min_buffer_size: Option<BigUint>,
 
     /// Can be used to specify that the port's buffer must be at least as large as the largest of
     /// some other set of port buffer sizes. LV2 symbols are used to identify the ports in the set.
-    buffer_as_large_as: BTreeSet<Symbol>
+    pub(crate) buffer_as_large_as: BTreeSet<Symbol>,
+
+    /// Indicates whether the port supports `time:Position` objects. This is only meaningful for
+    /// atom ports.
+    pub(crate) supports_time_position: bool,
+
+    /// Suggested default MIDI mapping for the port, if specified.
+    pub(crate) midi_binding: Option<MidiBinding>,
+
+    /// Port types that a morph port can take on, as specified by `morph:supportsType`.
+    pub(crate) morph_supported_types: KnownAndUnknownSet<PortType, UnknownPortType>,
+
+    /// Port type that a morph port currently has, as specified by `morph:currentType`.
+    pub(crate) morph_current_type: Option<PortType>,
+
+    /// Unit of the port's value, as specified by `units:unit`. Custom units (i.e. units not
+    /// identified by a standard unit IRI) are not represented.
+    pub(crate) unit: Option<Unit>
+}
+
+/// Gets the set of concrete data types that a port can have, i.e. the port types other than
+/// input/output direction and morphing.
+fn data_types() -> EnumSet<PortType> {
+    PortType::Audio | PortType::CV | PortType::Control | PortType::Atom
+}
+
+impl PortInfo {
+    /// Checks if the port is an input port. Note that a port can be both an input port and an
+    /// output port.
+    pub fn is_input(&self) -> bool {
+        self.port_types.contains_known(PortType::InputPort)
+    }
+
+    /// Checks if the port is an output port. Note that a port can be both an input port and an
+    /// output port.
+    pub fn is_output(&self) -> bool {
+        self.port_types.contains_known(PortType::OutputPort)
+    }
+
+    /// Gets the unit of the port's value as specified in the bundle, or
+    /// [`None`](std::option::Option::None) if no standard unit is specified. See
+    /// [`EffectivePort`](crate::bundle_model::effective::EffectivePort) for the unit implied by the
+    /// port's designations.
+    pub fn unit(&self) -> Option<Unit> {
+        self.unit
+    }
+
+    /// Gets all known port properties that apply to the port, including the properties implied by
+    /// the ones stated in the bundle (see
+    /// [`PORT_PROPERTIES_IMPLIED`](crate::bundle_model::implications::PORT_PROPERTIES_IMPLIED)). For
+    /// example, a trigger port is also treated as a toggle.
+    pub fn all_port_properties(&self) -> EnumSet<PortProperty> {
+        PORT_PROPERTIES_IMPLIED.reachable_nodes_from_multi(self.port_props.knowns())
+    }
+
+    /// Checks if a port property applies to the port, taking implications into account (see
+    /// [`all_port_properties`](self::PortInfo::all_port_properties)).
+    pub fn has_port_property(&self, port_property: PortProperty) -> bool {
+        self.all_port_properties().contains(port_property)
+    }
+
+    /// Checks if the port is an atom port that supports `time:Position` objects, i.e. an atom port
+    /// through which the host can send the full transport position.
+    pub fn supports_time_position(&self) -> bool {
+        self.supports_time_position
+    }
+
+    /// Gets the default value for the port, if specified.
+    pub fn default_value(&self) -> Option<&Literal> {
+        self.default_value.as_ref()
+    }
+
+    /// Gets the soft minimum value for the port, if specified.
+    pub fn min_value(&self) -> Option<&Literal> {
+        self.min_value.as_ref()
+    }
+
+    /// Gets the soft maximum value for the port, if specified.
+    pub fn max_value(&self) -> Option<&Literal> {
+        self.max_value.as_ref()
+    }
+
+    /// Gets a numeric view of the port's range, taking into account the port properties that
+    /// affect how values should be interpreted. See
+    /// [`ControlRange`](crate::bundle_model::control::ControlRange) for details.
+    pub fn control_range(&self) -> ControlRange {
+        ControlRange::of_port(self)
+    }
+
+    /// Gets a view of the port that merges the data stated in the bundle with the data implied by
+    /// the port's designations and properties. See
+    /// [`EffectivePort`](crate::bundle_model::effective::EffectivePort) for details.
+    pub fn effective(&self) -> EffectivePort<'_> {
+        EffectivePort::new(self)
+    }
+
+    /// Checks if the port is a morph port, i.e. a port whose data type can be changed. This
+    /// includes ports whose type is changed automatically by the plugin.
+    pub fn is_morph(&self) -> bool {
+        self.port_types.contains_known(PortType::Morph)
+            || self.port_types.contains_known(PortType::AutoMorph)
+    }
+
+    /// Gets the concrete data types (audio, CV, control or atom) that a morph port can take on, in
+    /// addition to its declared type. Types not understood by this crate are not included.
+    pub fn morph_supported_types(&self) -> EnumSet<PortType> {
+        self.morph_supported_types.knowns().intersection(data_types())
+    }
+
+    /// Gets the data type that a morph port currently has, as specified in the bundle, or
+    /// [`None`](std::option::Option::None) if not specified.
+    pub fn morph_current_type(&self) -> Option<PortType> {
+        self.morph_current_type
+    }
+
+    /// Selects the concrete data type (audio, CV, control or atom) that the host should use when
+    /// connecting the port, given the data types the host is able to connect. Returns
+    /// [`None`](std::option::Option::None) if no suitable type is available.
+    ///
+    /// For a regular port, the port's declared data type is used if the host can connect it. For
+    /// a port with `morph:MorphPort`, the host may choose any supported type; the current type is
+    /// preferred, followed by the declared type, followed by the other supported types in the
+    /// order of the [`PortType`](crate::bundle_model::constants::PortType) enum. For a port with
+    /// `morph:AutoMorphPort`, the plugin chooses the type, so only the current type (or declared
+    /// type, if no current type is specified) is considered.
+    pub fn resolve_type(&self, connectable: EnumSet<PortType>) -> Option<PortType> {
+        let connectable = connectable.intersection(data_types());
+        let declared_type = self.port_types.knowns().intersection(data_types()).iter().next();
+        let fixed_type = self.morph_current_type.or(declared_type);
+        if !self.port_types.contains_known(PortType::Morph) {
+            return fixed_type.filter(|port_type| connectable.contains(*port_type));
+        }
+
+        fixed_type.into_iter()
+            .chain(declared_type)
+            .chain(self.morph_supported_types().iter())
+            .find(|port_type| connectable.contains(*port_type))
+    }
+
+    /// Gets the MIDI message that the bundle suggests mapping to the port, e.g. a particular
+    /// controller on a particular channel. Returns [`None`](std::option::Option::None) if the
+    /// bundle does not specify a MIDI binding for the port, or if the specified binding is not a
+    /// valid MIDI channel voice message.
+    pub fn midi_binding(&self) -> Option<&MidiBinding> {
+        self.midi_binding.as_ref()
+    }
+
+    /// Gets the port's scale points, ordered by value. Scale points with equal values are ordered
+    /// by their labels.
+    pub fn scale_points_by_value(&self) -> Vec<&ScalePoint> {
+        let mut scale_points: Vec<_> = self.scale_pts.iter().collect();
+        scale_points.sort_by_key(|point| point.value);
+        scale_points
+    }
+
+    /// Gets the label of the scale point with exactly the given value, in the user's preferred
+    /// language (see [`ScalePoint::label`](self::ScalePoint::label)). Returns
+    /// [`None`](std::option::Option::None) if no scale point with the value has a label.
+    ///
+    /// # Parameters
+    /// - `value`: The control port value.
+    /// - `preferred_langs`: Language tags in order of decreasing preference.
+    pub fn scale_point_label(&self, value: f32, preferred_langs: &[LangTag]) -> Option<&Literal> {
+        self.scale_pts.iter()
+            .filter(|point| point.value() == value)
+            .find_map(|point| point.label(preferred_langs))
+    }
+
+    /// Gets the scale point whose value is closest to the given value. If two scale points are
+    /// equally close, the one with the lower value is returned. Returns
+    /// [`None`](std::option::Option::None) if the port has no scale points.
+    pub fn nearest_scale_point(&self, value: f32) -> Option<&ScalePoint> {
+        self.scale_points_by_value().into_iter()
+            .min_by_key(|point| OrderedFloat((point.value() - value).abs()))
+    }
 }
 
 impl IdentifiedBy<u32> for PortInfo {
@@ -105,8 +324,15 @@ impl OptionallyIdentifiedBy<Symbol> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, TypeRelation, PortType> for PortInfo {
     type BorrowedElt = PortType;
+    type SeqSetIter = EnumSetIter<PortType>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<PortType>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.port_types.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.port_types.knowns_iter()
     }
@@ -114,8 +340,15 @@ impl<'a> HasRelatedSet<'a, TypeRelation, PortType> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, TypeRelation, UnknownPortType> for PortInfo {
     type BorrowedElt = &'a UnknownPortType;
+    type SeqSetIter = btree_set::Iter<'a, UnknownPortType>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownPortType> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.port_types.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.port_types.unknowns_iter()
     }
@@ -123,8 +356,15 @@ impl<'a> HasRelatedSet<'a, TypeRelation, UnknownPortType> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, NameRelation, Literal> for PortInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.named_impl.names.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.named_impl.names.par_iter()
     }
@@ -132,8 +372,15 @@ impl<'a> HasRelatedSet<'a, NameRelation, Literal> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, ShortNameRelation, Literal> for PortInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.named_impl.short_names.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.named_impl.short_names.par_iter()
     }
@@ -141,8 +388,15 @@ impl<'a> HasRelatedSet<'a, ShortNameRelation, Literal> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, DocRelation, Literal> for PortInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.documented_impl.documentation.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.documented_impl.documentation.par_iter()
     }
@@ -150,26 +404,71 @@ impl<'a> HasRelatedSet<'a, DocRelation, Literal> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, GenericRelation, PortDesignation> for PortInfo {
     type BorrowedElt = PortDesignation;
+    type SeqSetIter = EnumSetIter<PortDesignation>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<PortDesignation>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.designations.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.designations.iter().par_bridge()
     }
+
+    fn has_elt(&'a self, to_check: &PortDesignation) -> bool {
+        self.designations.contains(*to_check)
+    }
 }
 
 impl<'a> HasRelatedSet<'a, GenericRelation, PortChannel> for PortInfo {
     type BorrowedElt = PortChannel;
+    type SeqSetIter = EnumSetIter<PortChannel>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<PortChannel>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.channel_designations.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.channel_designations.iter().par_bridge()
     }
 }
 
+impl<'a> HasRelatedSet<'a, GenericRelation, TimeDesignation> for PortInfo {
+    type BorrowedElt = TimeDesignation;
+    type SeqSetIter = EnumSetIter<TimeDesignation>;
+    #[cfg(feature = "rayon")]
+    type SetIter = IterBridge<EnumSetIter<TimeDesignation>>;
+
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.time_designations.iter()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn set_iter(&'a self) -> Self::SetIter {
+        self.time_designations.iter().par_bridge()
+    }
+
+    fn has_elt(&'a self, to_check: &TimeDesignation) -> bool {
+        self.time_designations.contains(*to_check)
+    }
+}
+
 impl<'a> HasRelatedSet<'a, GenericRelation, UnknownPortDesignation> for PortInfo {
     type BorrowedElt = &'a UnknownPortDesignation;
+    type SeqSetIter = btree_set::Iter<'a, UnknownPortDesignation>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownPortDesignation> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.unknown_designations.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.unknown_designations.par_iter()
     }
@@ -177,8 +476,15 @@ impl<'a> HasRelatedSet<'a, GenericRelation, UnknownPortDesignation> for PortInfo
 
 impl<'a> HasRelatedSet<'a, GenericRelation, PortProperty> for PortInfo {
     type BorrowedElt = PortProperty;
+    type SeqSetIter = EnumSetIter<PortProperty>;
+    #[cfg(feature = "rayon")]
     type SetIter = IterBridge<EnumSetIter<PortProperty>>;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.port_props.knowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.port_props.knowns_iter()
     }
@@ -186,8 +492,15 @@ impl<'a> HasRelatedSet<'a, GenericRelation, PortProperty> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, GenericRelation, UnknownPortProperty> for PortInfo {
     type BorrowedElt = &'a UnknownPortProperty;
+    type SeqSetIter = btree_set::Iter<'a, UnknownPortProperty>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<UnknownPortProperty> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.port_props.unknowns_seq_iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.port_props.unknowns_iter()
     }
@@ -195,9 +508,121 @@ impl<'a> HasRelatedSet<'a, GenericRelation, UnknownPortProperty> for PortInfo {
 
 impl<'a> HasRelatedSet<'a, GenericRelation, ScalePoint> for PortInfo {
     type BorrowedElt = &'a ScalePoint;
+    type SeqSetIter = btree_set::Iter<'a, ScalePoint>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<ScalePoint> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.scale_pts.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.scale_pts.par_iter()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use enumset::EnumSet;
+    use crate::rdf_util::{Graph, Node, Literal, LangTag};
+    use crate::bundle_model::Localized;
+    use crate::bundle_model::{TypeRelation, NameRelation, GenericRelation, LabelRelation};
+    use crate::bundle_model::constants::{IriConstant, PortType, PortDesignation, PortProperty};
+    use crate::bundle_model::unknowns::{UnknownPortType, UnknownPortProperty};
+    use crate::bundle_model::loader::{load_plugin, MORPH_SUPPORTS_TYPE, MORPH_CURRENT_TYPE, LV2_SCALE_POINT, LV2_NAME, LV2_DESIGNATION, LV2_PORT_PROPERTY, RDF_VALUE, RDFS_LABEL};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node, add_plugin, add_port, related_set};
+    use super::ScalePoint;
+
+    #[test]
+    fn scale_points_are_ordered_and_searched_by_value() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let port = add_port(&mut graph, &plugin, 0, "mode",
+            &[PortType::InputPort.iri(), PortType::Control.iri()]);
+        let labels = [
+            ("2", "Square", None), ("2", "Carré", Some("fr")), ("0", "Sine", None), ("1", "Saw", Some("en"))
+        ];
+        for (value, label, lang) in labels.iter() {
+            let point = Node::BlankNode(format!("point{}", value));
+            graph.insert(port.clone(), iri(LV2_SCALE_POINT), point.clone());
+            graph.insert(point.clone(), iri(RDF_VALUE), literal_node(value));
+            let literal = match lang {
+                Some(lang) => Literal::with_lang_tag(label.to_string(), LangTag::new(lang.to_string()).unwrap()),
+                None => Literal::new(label.to_string())
+            };
+            graph.insert(point, iri(RDFS_LABEL), Node::Literal(literal));
+        }
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        let port = &plugin.ports()[0];
+
+        let values: Vec<_> = port.scale_points_by_value().iter().map(|point| point.value()).collect();
+        assert_eq!(values, vec![0.0, 1.0, 2.0]);
+        assert_eq!(port.nearest_scale_point(1.4).unwrap().value(), 1.0);
+        assert_eq!(port.nearest_scale_point(1.5).unwrap().value(), 1.0);
+
+        let french = [LangTag::new(String::from("fr")).unwrap()];
+        assert_eq!(port.scale_point_label(2.0, &french).unwrap().value(), "Carré");
+        assert_eq!(port.scale_point_label(2.0, &[]).unwrap().value(), "Square");
+        assert_eq!(port.scale_point_label(1.0, &french).unwrap().value(), "Saw");
+        assert!(port.scale_point_label(3.0, &french).is_none());
+        assert_eq!(port.scale_points_by_value()[2].best_label(&french).unwrap().value(), "Carré");
+    }
+
+    #[test]
+    fn morph_port_types_are_resolved() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let morph = add_port(&mut graph, &plugin, 0, "morph",
+            &[PortType::InputPort.iri(), PortType::Control.iri(), PortType::Morph.iri()]);
+        graph.insert(morph.clone(), iri(MORPH_SUPPORTS_TYPE), iri_node(PortType::CV.iri()));
+        graph.insert(morph, iri(MORPH_SUPPORTS_TYPE), iri_node(PortType::Audio.iri()));
+        let auto_morph = add_port(&mut graph, &plugin, 1, "auto_morph",
+            &[PortType::OutputPort.iri(), PortType::Control.iri(), PortType::AutoMorph.iri()]);
+        graph.insert(auto_morph.clone(), iri(MORPH_SUPPORTS_TYPE), iri_node(PortType::CV.iri()));
+        graph.insert(auto_morph, iri(MORPH_CURRENT_TYPE), iri_node(PortType::CV.iri()));
+        add_port(&mut graph, &plugin, 2, "audio", &[PortType::OutputPort.iri(), PortType::Audio.iri()]);
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+
+        assert_eq!(plugin.resolve_port_types(EnumSet::all()),
+            vec![Some(PortType::Control), Some(PortType::CV), Some(PortType::Audio)]);
+        assert_eq!(plugin.resolve_port_types(PortType::Audio | PortType::CV),
+            vec![Some(PortType::Audio), Some(PortType::CV), Some(PortType::Audio)]);
+        assert_eq!(plugin.resolve_port_types(EnumSet::only(PortType::Control)),
+            vec![Some(PortType::Control), None, None]);
+    }
+
+    #[test]
+    fn sequential_and_parallel_related_sets_match() {
+        let mut graph = Graph::new();
+        let plugin = add_plugin(&mut graph, "urn:test:plugin");
+        let port = add_port(&mut graph, &plugin, 0, "gain",
+            &[PortType::InputPort.iri(), PortType::Control.iri(), "urn:test:port_type"]);
+        graph.insert(port.clone(), iri(LV2_NAME), literal_node("Gain"));
+        graph.insert(port.clone(), iri(LV2_DESIGNATION), iri_node(PortDesignation::Gain.iri()));
+        for property in &[PortProperty::Logarithmic.iri(), PortProperty::Trigger.iri(), "urn:test:property"] {
+            graph.insert(port.clone(), iri(LV2_PORT_PROPERTY), iri_node(property));
+        }
+        for value in &["0", "1"] {
+            let point = Node::BlankNode(format!("point{}", value));
+            graph.insert(port.clone(), iri(LV2_SCALE_POINT), point.clone());
+            graph.insert(point.clone(), iri(RDF_VALUE), literal_node(value));
+            graph.insert(point, iri(RDFS_LABEL), literal_node(value));
+        }
+        let plugin = load_plugin(&graph, &iri("urn:test:plugin")).unwrap();
+        let port = &plugin.ports()[0];
+
+        assert_eq!(related_set::<TypeRelation, PortType, _>(port),
+            vec![PortType::InputPort, PortType::Control].into_iter().collect());
+        assert_eq!(related_set::<TypeRelation, UnknownPortType, _>(port).len(), 1);
+        assert_eq!(related_set::<NameRelation, Literal, _>(port).len(), 1);
+        assert_eq!(related_set::<GenericRelation, PortDesignation, _>(port),
+            vec![PortDesignation::Gain].into_iter().collect());
+        assert_eq!(related_set::<GenericRelation, PortProperty, _>(port),
+            vec![PortProperty::Logarithmic, PortProperty::Trigger].into_iter().collect());
+        assert_eq!(related_set::<GenericRelation, UnknownPortProperty, _>(port).len(), 1);
+        let scale_points = related_set::<GenericRelation, ScalePoint, _>(port);
+        assert_eq!(scale_points.len(), 2);
+        assert_eq!(related_set::<LabelRelation, Literal, _>(scale_points.iter().next().unwrap()).len(), 1);
+    }
+}
@@ -1,7 +1,10 @@
 //! Representation of LV2 project information.
 
 use crate::rdf_util::{Iri, Literal};
+use std::collections::btree_set;
+#[cfg(feature = "rayon")]
 use std::collections::btree_set::BTreeSet;
+#[cfg(feature = "rayon")]
 use rayon::iter::IntoParallelRefIterator;
 use crate::bundle_model::{OptionallyIdentifiedBy, HasRelatedSet, NameRelation, ShortNameRelation};
 use crate::bundle_model::symbol::Symbol;
@@ -10,13 +13,13 @@ use crate::bundle_model::impl_util::NamedImpl;
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ProjectInfo {
     /// The IRI identifying the project, if specified.
-    iri: Option<Iri>,
+    pub(crate) iri: Option<Iri>,
 
     /// LV2 symbol identifying the project, if specified.
-    symbol: Option<Symbol>,
+    pub(crate) symbol: Option<Symbol>,
 
     /// Name and short name information.
-    named_impl: NamedImpl
+    pub(crate) named_impl: NamedImpl
 }
 
 impl OptionallyIdentifiedBy<Iri> for ProjectInfo {
@@ -33,8 +36,15 @@ impl OptionallyIdentifiedBy<Symbol> for ProjectInfo {
 
 impl<'a> HasRelatedSet<'a, NameRelation, Literal> for ProjectInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.named_impl.names.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.named_impl.names.par_iter()
     }
@@ -42,8 +52,15 @@ impl<'a> HasRelatedSet<'a, NameRelation, Literal> for ProjectInfo {
 
 impl<'a> HasRelatedSet<'a, ShortNameRelation, Literal> for ProjectInfo {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.named_impl.short_names.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.named_impl.short_names.par_iter()
     }
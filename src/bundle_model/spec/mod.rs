@@ -0,0 +1,297 @@
+//! Representation of LV2 specifications (e.g. the installed `lv2.lv2` and `atom.lv2` bundles, or
+//! third-party extensions), and the class hierarchy they define.
+
+use std::collections::{BTreeMap, BTreeSet, btree_set};
+use enumset::{EnumSet, EnumSetType};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use crate::rdf_util::{Graph, Iri, LangTag, Literal, lookup_literal};
+use crate::bundle_model::{ResourceVersion, HasRelatedSet, LabelRelation, IdentifiedBy};
+use crate::bundle_model::constants::{IriConstant, PluginType, PortType};
+use crate::bundle_model::unknowns::{UnknownPluginType, UnknownPortType};
+use crate::bundle_model::loader::{load_specifications, LoadError};
+
+/// Representation of a class (e.g. a plugin type or port type) defined by an LV2 specification.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClassInfo {
+    /// IRI identifying the class.
+    pub(crate) iri: Iri,
+
+    /// Labels for the class, as given by `rdfs:label`.
+    pub(crate) labels: BTreeSet<Literal>,
+
+    /// Direct superclasses of the class, as given by `rdfs:subClassOf`. Superclasses that are not
+    /// identified by an IRI (e.g. OWL restrictions) are not included.
+    pub(crate) superclasses: BTreeSet<Iri>
+}
+
+impl ClassInfo {
+    /// Gets a sequential iterator over the direct superclasses of the class.
+    pub fn superclasses_seq_iter(&self) -> impl Iterator<Item = &Iri> {
+        self.superclasses.iter()
+    }
+
+    /// Gets a (parallel) iterator over the direct superclasses of the class.
+    #[cfg(feature = "rayon")]
+    pub fn superclasses_iter(&self) -> impl ParallelIterator<Item = &Iri> {
+        self.superclasses.par_iter()
+    }
+
+    /// Adds the labels and superclasses of another description of the same class.
+    fn merge(&mut self, other: ClassInfo) {
+        self.labels.extend(other.labels);
+        self.superclasses.extend(other.superclasses);
+    }
+}
+
+impl IdentifiedBy<Iri> for ClassInfo {
+    fn id(&self) -> &Iri {
+        &self.iri
+    }
+}
+
+impl<'a> HasRelatedSet<'a, LabelRelation, Literal> for ClassInfo {
+    type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
+    type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
+
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.labels.iter()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn set_iter(&'a self) -> Self::SetIter {
+        self.labels.par_iter()
+    }
+}
+
+/// Representation of an LV2 specification, i.e. a resource of type `lv2:Specification`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SpecificationInfo {
+    /// IRI identifying the specification.
+    pub(crate) iri: Iri,
+
+    /// Specification version.
+    pub(crate) version: ResourceVersion,
+
+    /// Labels for the specification, as given by `rdfs:label`.
+    pub(crate) labels: BTreeSet<Literal>,
+
+    /// Classes defined by the specification, keyed by IRI.
+    pub(crate) classes: BTreeMap<Iri, ClassInfo>
+}
+
+impl SpecificationInfo {
+    /// Gets the specification version.
+    pub fn version(&self) -> &ResourceVersion {
+        &self.version
+    }
+
+    /// Gets the class with the specified IRI, or [`None`](std::option::Option::None) if the class
+    /// is not defined by this specification.
+    pub fn class(&self, iri: &Iri) -> Option<&ClassInfo> {
+        self.classes.get(iri)
+    }
+
+    /// Gets a sequential iterator over the classes defined by the specification.
+    pub fn classes_seq_iter(&self) -> impl Iterator<Item = &ClassInfo> {
+        self.classes.values()
+    }
+
+    /// Gets a (parallel) iterator over the classes defined by the specification.
+    #[cfg(feature = "rayon")]
+    pub fn classes_iter(&self) -> impl ParallelIterator<Item = &ClassInfo> {
+        self.classes.par_iter().map(|(_, class)| class)
+    }
+}
+
+impl IdentifiedBy<Iri> for SpecificationInfo {
+    fn id(&self) -> &Iri {
+        &self.iri
+    }
+}
+
+impl<'a> HasRelatedSet<'a, LabelRelation, Literal> for SpecificationInfo {
+    type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
+    type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
+
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.labels.iter()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn set_iter(&'a self) -> Self::SetIter {
+        self.labels.par_iter()
+    }
+}
+
+/// Representation of the LV2 specifications installed on a system, and the combined class
+/// hierarchy they define. This can be used to relate classes that this crate doesn't understand
+/// (e.g. an [`UnknownPluginType`](crate::bundle_model::unknowns::UnknownPluginType) defined by a
+/// third-party extension) to the classes it does understand, and to label them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpecCatalog {
+    /// Installed specifications, keyed by IRI. If a specification is installed more than once,
+    /// the most preferred version is kept.
+    specs: BTreeMap<Iri, SpecificationInfo>,
+
+    /// All classes defined by the installed specifications, keyed by IRI. If several
+    /// specifications describe the same class, their descriptions are merged.
+    classes: BTreeMap<Iri, ClassInfo>
+}
+
+impl SpecCatalog {
+    /// Constructs an empty catalog.
+    pub fn new() -> Self {
+        SpecCatalog {
+            specs: BTreeMap::new(),
+            classes: BTreeMap::new()
+        }
+    }
+
+    /// Adds a specification to the catalog. If the same specification is already present, the
+    /// version chosen by
+    /// [`ResourceVersion::cmp_preference`](crate::bundle_model::ResourceVersion::cmp_preference)
+    /// is kept, but the classes of both versions remain in the class hierarchy.
+    pub fn insert(&mut self, spec: SpecificationInfo) {
+        for class in spec.classes.values() {
+            match self.classes.get_mut(&class.iri) {
+                Some(existing) => existing.merge(class.clone()),
+                None => {
+                    self.classes.insert(class.iri.clone(), class.clone());
+                }
+            }
+        }
+
+        let replace = self.specs.get(&spec.iri)
+            .map_or(true, |existing| spec.version.cmp_preference(&existing.version).is_gt());
+        if replace {
+            self.specs.insert(spec.iri.clone(), spec);
+        }
+    }
+
+    /// Loads all specifications described in a bundle and adds them to the catalog.
+    ///
+    /// # Parameters
+    /// - `graph`: Graph containing the bundle's RDF data.
+    ///
+    /// # Errors
+    /// Returns an error if any specification in the bundle cannot be loaded. In that case, no
+    /// specifications from the bundle are added.
+    pub fn add_bundle(&mut self, graph: &Graph) -> Result<(), LoadError> {
+        for spec in load_specifications(graph)? {
+            self.insert(spec);
+        }
+        Ok(())
+    }
+
+    /// Gets the specification with the specified IRI, or [`None`](std::option::Option::None) if
+    /// it is not installed.
+    pub fn specification(&self, iri: &Iri) -> Option<&SpecificationInfo> {
+        self.specs.get(iri)
+    }
+
+    /// Gets the class with the specified IRI, or [`None`](std::option::Option::None) if no
+    /// installed specification defines it.
+    pub fn class(&self, iri: &Iri) -> Option<&ClassInfo> {
+        self.classes.get(iri)
+    }
+
+    /// Gets a sequential iterator over all classes defined by the installed specifications.
+    pub fn classes_seq_iter(&self) -> impl Iterator<Item = &ClassInfo> {
+        self.classes.values()
+    }
+
+    /// Gets a (parallel) iterator over all classes defined by the installed specifications.
+    #[cfg(feature = "rayon")]
+    pub fn classes_iter(&self) -> impl ParallelIterator<Item = &ClassInfo> {
+        self.classes.par_iter().map(|(_, class)| class)
+    }
+
+    /// Gets the label of a class that best matches the preferred languages (see
+    /// [`lookup_literal`](crate::rdf_util::lookup_literal)), or
+    /// [`None`](std::option::Option::None) if the class is unknown or has no labels.
+    pub fn class_label(&self, iri: &Iri, preferred_langs: &[LangTag]) -> Option<&Literal> {
+        self.classes.get(iri).and_then(|class| lookup_literal(&class.labels, preferred_langs))
+    }
+
+    /// Gets all direct and indirect superclasses of a class. The class itself is not included,
+    /// unless the hierarchy contains a cycle through it.
+    pub fn all_superclasses(&self, iri: &Iri) -> BTreeSet<Iri> {
+        let mut found = BTreeSet::new();
+        let mut stack = vec![iri];
+        while let Some(current) = stack.pop() {
+            if let Some(class) = self.classes.get(current) {
+                for superclass in &class.superclasses {
+                    if found.insert(superclass.clone()) {
+                        stack.push(superclass);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    /// Checks if a class is a direct or indirect subclass of another class. Following RDF Schema,
+    /// every class is considered a subclass of itself.
+    pub fn is_subclass_of(&self, subclass: &Iri, superclass: &Iri) -> bool {
+        subclass == superclass || self.all_superclasses(subclass).contains(superclass)
+    }
+
+    /// Gets the classes understood by this crate (e.g.
+    /// [`PluginType`](crate::bundle_model::constants::PluginType)s) that a class belongs to,
+    /// including the class itself if it is understood.
+    pub fn known_superclasses<K>(&self, iri: &Iri) -> EnumSet<K>
+        where K: EnumSetType + IriConstant
+    {
+        std::iter::once(iri).chain(self.all_superclasses(iri).iter())
+            .filter_map(|class| K::from_iri(class.as_str()))
+            .collect()
+    }
+
+    /// Gets the known plugin types that an unknown plugin type is a subclass of.
+    pub fn known_plugin_types(&self, plugin_type: &UnknownPluginType) -> EnumSet<PluginType> {
+        self.known_superclasses(plugin_type.id())
+    }
+
+    /// Gets the known port types that an unknown port type is a subclass of.
+    pub fn known_port_types(&self, port_type: &UnknownPortType) -> EnumSet<PortType> {
+        self.known_superclasses(port_type.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdf_util::Node;
+    use crate::bundle_model::loader::{RDF_TYPE, RDFS_LABEL, RDFS_SUBCLASS_OF, RDFS_CLASS, LV2_SPECIFICATION, LV2_MINOR_VERSION};
+    use crate::bundle_model::test_util::{iri, iri_node, literal_node};
+
+    #[test]
+    fn unknown_classes_are_related_to_known_ones() {
+        let mut graph = Graph::new();
+        let spec = iri_node("http://example.org/ext/filters");
+        graph.insert(spec.clone(), iri(RDF_TYPE), iri_node(LV2_SPECIFICATION));
+        graph.insert(spec, iri(LV2_MINOR_VERSION), literal_node("2"));
+        let ladder = iri_node("http://example.org/ext/filters#LadderFilter");
+        graph.insert(ladder.clone(), iri(RDF_TYPE), iri_node(RDFS_CLASS));
+        graph.insert(ladder.clone(), iri(RDFS_SUBCLASS_OF), iri_node(PluginType::Lowpass.iri()));
+        graph.insert(ladder.clone(), iri(RDFS_SUBCLASS_OF), Node::BlankNode(String::from("restriction")));
+        graph.insert(ladder, iri(RDFS_LABEL), literal_node("Ladder filter"));
+        let moog = iri_node("http://example.org/ext/filters#MoogLadderFilter");
+        graph.insert(moog, iri(RDFS_SUBCLASS_OF), iri_node("http://example.org/ext/filters#LadderFilter"));
+
+        let mut catalog = SpecCatalog::new();
+        catalog.add_bundle(&graph).unwrap();
+        assert_eq!(catalog.specification(&iri("http://example.org/ext/filters")).unwrap().classes.len(), 2);
+
+        let moog = UnknownPluginType::new(iri("http://example.org/ext/filters#MoogLadderFilter"));
+        assert_eq!(catalog.known_plugin_types(&moog), EnumSet::only(PluginType::Lowpass));
+        assert!(catalog.is_subclass_of(moog.id(), &iri("http://example.org/ext/filters#LadderFilter")));
+        assert_eq!(catalog.class_label(&iri("http://example.org/ext/filters#LadderFilter"), &[]).unwrap().value(),
+            "Ladder filter");
+    }
+}
@@ -28,6 +28,7 @@ impl Symbol {
     ///
     /// # Errors
     /// Returns an error if `string` is not a valid LV2 symbol string.
+    #[allow(clippy::result_unit_err)]
     pub fn new(string: String) -> Result<Symbol, SymbolError> {
         if SYMBOL_REGEX.is_match(&string) {
             Ok(Symbol {
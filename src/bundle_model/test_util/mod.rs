@@ -0,0 +1,66 @@
+//! Helpers for building bundle RDF data in unit tests.
+
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+use crate::rdf_util::{Graph, Iri, Literal, Node};
+use crate::bundle_model::HasRelatedSet;
+use crate::bundle_model::loader::{RDF_TYPE, LV2_PLUGIN, LV2_BINARY, LV2_PORT, LV2_INDEX, LV2_SYMBOL};
+
+/// Constructs an IRI, panicking if it is invalid.
+pub(crate) fn iri(text: &str) -> Iri {
+    Iri::new(text.to_string()).unwrap()
+}
+
+/// Constructs an IRI node, panicking if the IRI is invalid.
+pub(crate) fn iri_node(text: &str) -> Node {
+    Node::Iri(iri(text))
+}
+
+/// Constructs a plain literal node.
+pub(crate) fn literal_node(text: &str) -> Node {
+    Node::Literal(Literal::new(text.to_string()))
+}
+
+/// Adds a minimal plugin description to a graph, returning the plugin node.
+pub(crate) fn add_plugin(graph: &mut Graph, plugin_iri: &str) -> Node {
+    let plugin = iri_node(plugin_iri);
+    graph.insert(plugin.clone(), iri(RDF_TYPE), iri_node(LV2_PLUGIN));
+    graph.insert(plugin.clone(), iri(LV2_BINARY), iri_node("plugin.so"));
+    plugin
+}
+
+/// Adds a port with the specified index, symbol and types to a plugin description, returning the
+/// port node. The port is represented by a blank node.
+pub(crate) fn add_port(graph: &mut Graph, plugin: &Node, index: u32, symbol: &str, types: &[&str])
+    -> Node
+{
+    let port = Node::BlankNode(format!("port{}", index));
+    graph.insert(plugin.clone(), iri(LV2_PORT), port.clone());
+    graph.insert(port.clone(), iri(LV2_INDEX), literal_node(&index.to_string()));
+    graph.insert(port.clone(), iri(LV2_SYMBOL), literal_node(symbol));
+    for port_type in types {
+        graph.insert(port.clone(), iri(RDF_TYPE), iri_node(port_type));
+    }
+    port
+}
+
+/// Collects a related set through its sequential iterator, panicking if the iterator repeats an
+/// element. With the `rayon` feature, also panics if the parallel iterator yields a different set.
+pub(crate) fn related_set<'a, R, T, S>(subject: &'a S) -> BTreeSet<T>
+    where S: HasRelatedSet<'a, R, T>,
+          T: Send + Sync + Ord + Clone + Debug
+{
+    let elts: Vec<T> = subject.seq_set_iter().map(|elt| elt.borrow().clone()).collect();
+    let set: BTreeSet<T> = elts.iter().cloned().collect();
+    assert_eq!(set.len(), elts.len(), "sequential iterator repeated an element");
+    #[cfg(feature = "rayon")]
+    {
+        let par_elts: Vec<T> = subject.set_iter().map(|elt| elt.borrow().clone()).collect();
+        assert_eq!(par_elts.len(), elts.len(), "parallel iterator yielded a different number of elements");
+        assert_eq!(par_elts.into_iter().collect::<BTreeSet<T>>(), set);
+    }
+    set
+}
@@ -6,7 +6,8 @@
 use crate::bundle_model::{IdentifiedBy, OptionallyIdentifiedBy, HasRelatedSet, LabelRelation};
 use crate::bundle_model::symbol::Symbol;
 use crate::rdf_util::{Iri, Literal};
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, btree_set};
+#[cfg(feature = "rayon")]
 use rayon::iter::IntoParallelRefIterator;
 
 // TODO: Maybe add representation of literals and blank nodes in the UnknownX types? Handling of
@@ -227,8 +228,15 @@ impl OptionallyIdentifiedBy<Symbol> for UnknownUnit {
 
 impl<'a> HasRelatedSet<'a, LabelRelation, Literal> for UnknownUnit {
     type BorrowedElt = &'a Literal;
+    type SeqSetIter = btree_set::Iter<'a, Literal>;
+    #[cfg(feature = "rayon")]
     type SetIter = <BTreeSet<Literal> as IntoParallelRefIterator<'a>>::Iter;
 
+    fn seq_set_iter(&'a self) -> Self::SeqSetIter {
+        self.labels.iter()
+    }
+
+    #[cfg(feature = "rayon")]
     fn set_iter(&'a self) -> Self::SetIter {
         self.labels.par_iter()
     }
@@ -0,0 +1,199 @@
+//! Representation of graphs whose nodes are arbitrary hashable values, such as IRIs.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use enumset::{EnumSetType, EnumSet};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use crate::enum_graph::EnumSetDiGraph;
+
+/// Represents a directed graph with unlabeled edges (self loops allowed), where the nodes are
+/// arbitrary hashable values. Unlike [`EnumSetDiGraph`](crate::enum_graph::EnumSetDiGraph), the
+/// set of nodes is not fixed at compile time, so this type can represent relations that involve
+/// resources this crate doesn't understand (e.g. an
+/// [`UnknownPluginType`](crate::bundle_model::unknowns::UnknownPluginType) defined by a
+/// third-party extension). An enum graph can be embedded by giving each enum value a key (e.g.
+/// its IRI), so that relations among known and unknown resources can be queried together.
+///
+/// # Parameters
+/// - `N`: Type of node to use for the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyedDiGraph<N: Hash + Eq> {
+    /// An adjacency set for each node in the graph.
+    adj_sets: HashMap<N, HashSet<N>>
+}
+
+impl<N: Hash + Eq + Clone> KeyedDiGraph<N> {
+    /// Constructs a new directed graph with no nodes.
+    pub fn new() -> Self {
+        KeyedDiGraph {
+            adj_sets: HashMap::new()
+        }
+    }
+
+    /// Constructs a graph with the same structure as an enum graph, using the specified function
+    /// to map enum values to nodes.
+    ///
+    /// # Parameters
+    /// - `graph`: Graph to copy.
+    /// - `key`: Function giving the node for each enum value. Distinct enum values should map to
+    ///   distinct nodes.
+    pub fn from_enum_graph<T, F>(graph: &EnumSetDiGraph<T>, key: F) -> Self
+        where T: EnumSetType,
+              F: Fn(T) -> N
+    {
+        let mut output = KeyedDiGraph::new();
+        output.insert_enum_graph(graph, key);
+        output
+    }
+
+    /// Checks if the specified node is in the graph.
+    pub fn has_node(&self, node: &N) -> bool {
+        self.adj_sets.contains_key(node)
+    }
+
+    /// Checks if the specified directed edge is present in the graph.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the edge.
+    /// - `to`: End node for the edge.
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        self.adj_sets.get(from).is_some_and(|adj_set| adj_set.contains(to))
+    }
+
+    /// Checks if the graph contains a directed path from the specified start node to the specified
+    /// end node. Zero-length paths are included, so this will always return true if `from` and `to`
+    /// are the same node, even if the node is not in the graph.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the path.
+    /// - `to`: End node for the path.
+    pub fn has_path(&self, from: &N, to: &N) -> bool {
+        self.reachable_nodes(from).contains(to)
+    }
+
+    /// Gets a sequential iterator over the nodes in the graph.
+    pub fn nodes_seq_iter(&self) -> impl Iterator<Item = &N> {
+        self.adj_sets.keys()
+    }
+
+    /// Gets a sequential iterator over the nodes directly reachable by a one-edge directed path
+    /// starting at the specified node. The iterator is empty if the node is not in the graph.
+    pub fn adjacent_nodes_seq_iter<'a>(&'a self, from: &N) -> impl Iterator<Item = &'a N> {
+        self.adj_sets.get(from).into_iter().flatten()
+    }
+
+    /// Gets a (parallel) iterator over the nodes in the graph.
+    #[cfg(feature = "rayon")]
+    pub fn nodes_iter(&self) -> impl ParallelIterator<Item = &N>
+        where N: Sync
+    {
+        self.adj_sets.par_iter().map(|(node, _)| node)
+    }
+
+    /// Gets a (parallel) iterator over the nodes directly reachable by a one-edge directed path
+    /// starting at the specified node. The iterator is empty if the node is not in the graph.
+    #[cfg(feature = "rayon")]
+    pub fn adjacent_nodes_iter<'a>(&'a self, from: &N) -> impl ParallelIterator<Item = &'a N>
+        where N: Sync
+    {
+        self.adj_sets.get(from).into_par_iter().flat_map(|adj_set| adj_set.par_iter())
+    }
+
+    /// Finds the set of nodes reachable by any directed path starting at the specified node.
+    /// Zero-length paths are included, so the returned set will always contain the start node.
+    ///
+    /// # Parameters
+    /// - `from`: Node to start from.
+    pub fn reachable_nodes(&self, from: &N) -> HashSet<N> {
+        // Perform a depth-first search.
+        let mut visited = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(node) = stack.pop() {
+            if visited.insert(node.clone()) {
+                stack.extend(self.adj_sets.get(node).into_iter().flatten());
+            }
+        }
+
+        visited
+    }
+
+    /// Adds the specified node to the graph if it is not present.
+    pub fn insert_node(&mut self, node: N) {
+        self.adj_sets.entry(node).or_default();
+    }
+
+    /// Adds the specified directed edge to the graph if it is not present. The end points are
+    /// added to the graph if necessary.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the edge.
+    /// - `to`: End node for the edge.
+    pub fn insert_edge(&mut self, from: N, to: N) {
+        self.insert_node(to.clone());
+        self.adj_sets.entry(from).or_default().insert(to);
+    }
+
+    /// Adds all the nodes and edges of an enum graph, using the specified function to map enum
+    /// values to nodes.
+    ///
+    /// # Parameters
+    /// - `graph`: Graph containing the nodes and edges to add.
+    /// - `key`: Function giving the node for each enum value. Distinct enum values should map to
+    ///   distinct nodes.
+    pub fn insert_enum_graph<T, F>(&mut self, graph: &EnumSetDiGraph<T>, key: F)
+        where T: EnumSetType,
+              F: Fn(T) -> N
+    {
+        for from in EnumSet::<T>::all() {
+            self.insert_node(key(from));
+            for to in graph.adjacent_nodes(from) {
+                self.insert_edge(key(from), key(to));
+            }
+        }
+    }
+
+    /// Reverses the directions of all edges in the graph.
+    pub fn reverse(&self) -> Self {
+        let mut output = KeyedDiGraph::new();
+        for (from, adj_set) in &self.adj_sets {
+            output.insert_node(from.clone());
+            for to in adj_set {
+                output.insert_edge(to.clone(), from.clone());
+            }
+        }
+
+        output
+    }
+}
+
+impl<N: Hash + Eq + Clone> Default for KeyedDiGraph<N> {
+    fn default() -> Self {
+        KeyedDiGraph::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdf_util::Iri;
+    use crate::bundle_model::constants::{IriConstant, PluginType};
+    use crate::bundle_model::implications::PLUGIN_TYPES_IMPLIED;
+    use crate::bundle_model::test_util::iri;
+
+    #[test]
+    fn unknown_nodes_relate_to_embedded_enum_graph() {
+        let known_iri = |plugin_type: PluginType| Iri::new(plugin_type.iri().to_string()).unwrap();
+        let mut graph = KeyedDiGraph::from_enum_graph(&PLUGIN_TYPES_IMPLIED, known_iri);
+        let ladder = iri("http://example.org/ext#Ladder");
+        graph.insert_edge(ladder.clone(), known_iri(PluginType::Lowpass));
+
+        let reachable = graph.reachable_nodes(&ladder);
+        assert!(reachable.contains(&known_iri(PluginType::Filter)));
+        assert!(!reachable.contains(&known_iri(PluginType::Highpass)));
+        assert!(graph.has_path(&ladder, &ladder));
+        assert!(graph.reverse().has_edge(&known_iri(PluginType::Lowpass), &ladder));
+        assert_eq!(graph.adjacent_nodes_seq_iter(&ladder).count(), 1);
+        assert_eq!(graph.nodes_seq_iter().count(), EnumSet::<PluginType>::all().len() + 1);
+    }
+}
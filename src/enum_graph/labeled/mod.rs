@@ -0,0 +1,157 @@
+//! Representation of graphs of compile-time constant values, with labeled edges.
+
+use enumset::{EnumSetType, EnumSet};
+use enum_map::{Enum, EnumMap};
+use crate::enum_graph::EnumSetDiGraph;
+
+/// Represents a directed graph with labeled edges (self loops allowed), where both the nodes and
+/// the labels are compile-time constants defined by [`EnumSetType`](enumset::EnumSetType) enums.
+/// Labels typically identify the relation an edge represents (e.g. "is a subclass of", "implies"
+/// or "conflicts with"). The same pair of nodes may be connected by several edges with different
+/// labels.
+///
+/// # Parameters
+/// - `T`: Type of node to use for the graph. The graph always contains all nodes in the enum,
+///   though some nodes might not be connected to any other nodes.
+/// - `L`: Type of edge label to use for the graph.
+#[derive(Debug, PartialEq, Eq, Hash, Default)]
+pub struct LabeledEnumSetDiGraph<T, L>
+    where T: EnumSetType,
+          L: EnumSetType + Enum<EnumSetDiGraph<T>>
+{
+    /// The unlabeled graph formed by the edges with each label.
+    layers: EnumMap<L, EnumSetDiGraph<T>>
+}
+
+impl<T, L> LabeledEnumSetDiGraph<T, L>
+    where T: EnumSetType,
+          L: EnumSetType + Enum<EnumSetDiGraph<T>>
+{
+    /// Constructs a new directed graph with no edges.
+    pub fn new() -> Self {
+        LabeledEnumSetDiGraph {
+            layers: EnumMap::from(|_| EnumSetDiGraph::new())
+        }
+    }
+
+    /// Checks if the specified directed edge is present in the graph.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the edge.
+    /// - `label`: Label of the edge.
+    /// - `to`: End node for the edge.
+    pub fn has_edge(&self, from: T, label: L, to: T) -> bool {
+        self.layers[label].has_edge(from, to)
+    }
+
+    /// Finds the labels of all edges from the specified start node to the specified end node.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the edges.
+    /// - `to`: End node for the edges.
+    pub fn edge_labels(&self, from: T, to: T) -> EnumSet<L> {
+        EnumSet::all().iter()
+            .filter(|&label| self.layers[label].has_edge(from, to))
+            .collect()
+    }
+
+    /// Finds the set of nodes directly reachable from the specified node by an edge with any of
+    /// the specified labels. The start node is not included unless it has a matching self loop.
+    ///
+    /// # Parameters
+    /// - `from`: Node to start from.
+    /// - `labels`: Labels of the edges to follow.
+    pub fn adjacent_nodes(&self, from: T, labels: EnumSet<L>) -> EnumSet<T> {
+        labels.iter()
+            .fold(EnumSet::empty(), |nodes, label| nodes.union(self.layers[label].adjacent_nodes(from)))
+    }
+
+    /// Finds the set of nodes reachable from the specified node by any directed path consisting of
+    /// edges with the specified labels. Zero-length paths are included, so the returned set will
+    /// always contain the start node.
+    ///
+    /// # Parameters
+    /// - `from`: Node to start from.
+    /// - `labels`: Labels of the edges to follow.
+    pub fn reachable_nodes(&self, from: T, labels: EnumSet<L>) -> EnumSet<T> {
+        self.subgraph(labels).reachable_nodes(from)
+    }
+
+    /// Adds the specified directed edge to the graph if it is not present.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the edge.
+    /// - `label`: Label of the edge.
+    /// - `to`: End node for the edge.
+    pub fn insert_edge(&mut self, from: T, label: L, to: T) {
+        self.layers[label].insert_edge(from, to);
+    }
+
+    /// Adds all the edges of an unlabeled graph, with the specified label.
+    ///
+    /// # Parameters
+    /// - `label`: Label to give the added edges.
+    /// - `graph`: Graph containing the edges to add.
+    pub fn insert_graph(&mut self, label: L, graph: &EnumSetDiGraph<T>) {
+        self.layers[label] = self.layers[label].union(graph);
+    }
+
+    /// Gets the unlabeled graph formed by the edges with the specified label.
+    pub fn layer(&self, label: L) -> &EnumSetDiGraph<T> {
+        &self.layers[label]
+    }
+
+    /// Constructs the unlabeled graph formed by the edges with any of the specified labels.
+    pub fn subgraph(&self, labels: EnumSet<L>) -> EnumSetDiGraph<T> {
+        labels.iter()
+            .fold(EnumSetDiGraph::new(), |graph, label| graph.union(&self.layers[label]))
+    }
+}
+
+impl<T, L> Clone for LabeledEnumSetDiGraph<T, L>
+    where T: EnumSetType,
+          L: EnumSetType + Enum<EnumSetDiGraph<T>>,
+          EnumMap<L, EnumSetDiGraph<T>>: Clone
+{
+    fn clone(&self) -> Self {
+        LabeledEnumSetDiGraph {
+            layers: self.layers.clone()
+        }
+    }
+}
+
+impl<T, L> Copy for LabeledEnumSetDiGraph<T, L>
+    where T: EnumSetType,
+          L: EnumSetType + Enum<EnumSetDiGraph<T>>,
+          EnumMap<L, EnumSetDiGraph<T>>: Copy
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle_model::constants::PortProperty;
+
+    #[derive(Debug, EnumSetType, Enum)]
+    enum Relation {
+        Implies,
+        ConflictsWith
+    }
+
+    #[test]
+    fn labels_are_tracked_per_edge() {
+        let mut graph = LabeledEnumSetDiGraph::new();
+        graph.insert_edge(PortProperty::Trigger, Relation::Implies, PortProperty::Toggle);
+        graph.insert_edge(PortProperty::Toggle, Relation::Implies, PortProperty::IntOnly);
+        graph.insert_edge(PortProperty::Toggle, Relation::ConflictsWith, PortProperty::Logarithmic);
+
+        assert!(graph.has_edge(PortProperty::Trigger, Relation::Implies, PortProperty::Toggle));
+        assert!(!graph.has_edge(PortProperty::Trigger, Relation::ConflictsWith, PortProperty::Toggle));
+        assert_eq!(graph.edge_labels(PortProperty::Toggle, PortProperty::Logarithmic),
+            EnumSet::only(Relation::ConflictsWith));
+        assert_eq!(graph.reachable_nodes(PortProperty::Trigger, EnumSet::only(Relation::Implies)),
+            PortProperty::Trigger | PortProperty::Toggle | PortProperty::IntOnly);
+        assert_eq!(graph.adjacent_nodes(PortProperty::Toggle, EnumSet::all()),
+            PortProperty::IntOnly | PortProperty::Logarithmic);
+        assert_eq!(graph.layer(Relation::ConflictsWith).adjacent_nodes(PortProperty::Trigger), EnumSet::empty());
+    }
+}
@@ -1,38 +1,130 @@
-//! Representation of graphs of compile-time constant values.
+//! Representation of graphs of compile-time constant values, along with labeled-edge and
+//! arbitrarily keyed variants.
 
 use std::cmp::Ordering;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
 use enumset::{EnumSetType, EnumSet};
-use enum_map::{Enum, EnumMap};
-use std::iter::{FromIterator, once};
+use std::iter::FromIterator;
+#[cfg(feature = "rayon")]
+use std::iter::once;
+#[cfg(feature = "rayon")]
 use rayon::iter::{ParallelIterator, FromParallelIterator, IntoParallelIterator};
 
+pub mod labeled;
+pub mod keyed;
+
+/// Formats a string as a quoted Graphviz DOT identifier.
+pub(crate) fn dot_string(text: &str) -> String {
+    let mut output = String::with_capacity(text.len() + 2);
+    output.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            _ => output.push(c)
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Error type returned when an operation requires an acyclic graph, but the graph contains a
+/// cycle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CycleError<T> {
+    /// A cycle found in the graph. Each node has an edge to the next, and the last node has an edge
+    /// to the first.
+    pub cycle: Vec<T>
+}
+
+/// Maximum number of nodes in an [`EnumSetDiGraph`](self::EnumSetDiGraph). This is the largest
+/// number of variants supported by [`EnumSet`](enumset::EnumSet).
+const MAX_NODES: usize = 128;
+
+/// Constructs an [`EnumSetDiGraph`](crate::enum_graph::EnumSetDiGraph) from a list of edges
+/// between variants of an enum. The result can be used to initialize a `const` or `static`.
+///
+/// ```
+/// # #[macro_use] extern crate enumset;
+/// # #[macro_use] extern crate lv2_se_bundle;
+/// # use lv2_se_bundle::enum_graph::EnumSetDiGraph;
+/// # #[derive(Debug, EnumSetType)] enum Node { A, B, C }
+/// # fn main() {
+/// static GRAPH: EnumSetDiGraph<Node> = enum_graph!(Node { A => B, B => C }).transitive_closure();
+/// assert!(GRAPH.has_edge(Node::A, Node::C));
+/// # }
+/// ```
+#[macro_export]
+macro_rules! enum_graph {
+    ($type:ident { $($from:ident => $to:ident),* $(,)* }) => {
+        $crate::enum_graph::EnumSetDiGraph::<$type>::from_edge_indices(
+            &[$(($type::$from as u8, $type::$to as u8)),*]
+        )
+    };
+}
+
 /// Represents a directed graph with unlabeled edges (self loops allowed), where the nodes are
 /// compile-time constants defined by an [`EnumSetType`](enumset::EnumSetType) enum.
 ///
+/// A graph can be constructed, and its transitive closure computed, in a `const` context (see the
+/// [`enum_graph`](enum_graph) macro), so a `static` graph needs no initialization at runtime.
+///
 /// # Parameters
 /// - `T`: Type of node to use for the graph. The graph always contains all nodes in the enum,
 ///   though some nodes might not be connected to any other nodes.
-#[derive(Debug, PartialEq, Eq, Hash, Default)]
-pub struct EnumSetDiGraph<T: EnumSetType + Enum<EnumSet<T>>> {
-    /// An adjacency set for each node in the enum.
-    adj_sets: EnumMap<T, EnumSet<T>>
+#[derive(Clone, Copy)]
+pub struct EnumSetDiGraph<T: EnumSetType> {
+    /// An adjacency bit set for each node, indexed by the node's bit position in an
+    /// [`EnumSet`](enumset::EnumSet). Bits that don't correspond to a node may be set (e.g. by
+    /// [`transitive_closure`](self::EnumSetDiGraph::transitive_closure)), and are ignored.
+    adj_bits: [u128; MAX_NODES],
+
+    node_type: PhantomData<T>
 }
 
-impl<T: EnumSetType + Enum<EnumSet<T>>> EnumSetDiGraph<T> {
+impl<T: EnumSetType> EnumSetDiGraph<T> {
     /// Constructs a new directed graph with no edges.
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
         EnumSetDiGraph {
-            adj_sets: EnumMap::from(|_| EnumSet::empty())
+            adj_bits: [0; MAX_NODES],
+            node_type: PhantomData
         }
     }
 
+    /// Constructs a directed graph from a list of edges, with each node given by its bit position
+    /// in an [`EnumSet`](enumset::EnumSet) (i.e. its discriminant). This is an implementation
+    /// detail of the [`enum_graph`](enum_graph) macro, which should be used instead.
+    ///
+    /// # Parameters
+    /// - `edges`: (start node, end node) pairs for the edges.
+    ///
+    /// # Panics
+    /// Panics (at compile time, in a `const` context) if a bit position is out of range for an
+    /// [`EnumSet`](enumset::EnumSet).
+    #[doc(hidden)]
+    pub const fn from_edge_indices(edges: &[(u8, u8)]) -> Self {
+        let mut output = EnumSetDiGraph::new();
+        let mut index = 0;
+        while index < edges.len() {
+            let (from, to) = edges[index];
+            assert!((from as usize) < MAX_NODES && (to as usize) < MAX_NODES,
+                "enum graph node out of range");
+            output.adj_bits[from as usize] |= 1 << to;
+            index += 1;
+        }
+        output
+    }
+
     /// Checks if the specified directed edge is present in the graph.
     ///
     /// # Parameters
     /// - `from`: Start node for the edge.
     /// - `to`: End node for the edge.
     pub fn has_edge(&self, from: T, to: T) -> bool {
-        self.adj_sets[from].contains(to)
+        self.adjacent_nodes(from).contains(to)
     }
 
     /// Checks if the graph contains a directed path from the specified start node to the specified
@@ -50,7 +142,7 @@ impl<T: EnumSetType + Enum<EnumSet<T>>> EnumSetDiGraph<T> {
     /// specified node. Zero-length paths are not included, so the returned set will not contain the
     /// start node unless the start node has a self loop.
     pub fn adjacent_nodes(&self, from: T) -> EnumSet<T> {
-        self.adj_sets[from]
+        EnumSet::from_bits(self.adj_bits[Self::index(from)] & EnumSet::<T>::all().to_bits())
     }
 
     /// Finds the set of nodes reachable by any directed path starting at the specified node.
@@ -59,20 +151,7 @@ impl<T: EnumSetType + Enum<EnumSet<T>>> EnumSetDiGraph<T> {
     /// # Parameters
     /// - `from`: Node to start from.
     pub fn reachable_nodes(&self, from: T) -> EnumSet<T> {
-        // Perform a depth-first search.
-        let mut visited = EnumSet::empty();
-        let mut stack = vec![from];
-        while !stack.is_empty() {
-            let neighbor = stack.pop().unwrap();
-            if !visited.contains(neighbor) {
-                visited.insert(neighbor);
-                for next in self.adj_sets[neighbor] {
-                    stack.push(next)
-                }
-            }
-        }
-
-        visited
+        self.reachable_nodes_from_multi(EnumSet::only(from))
     }
 
     /// Finds the set of nodes reachable starting from any of the specified start nodes. Zero-length
@@ -81,8 +160,16 @@ impl<T: EnumSetType + Enum<EnumSet<T>>> EnumSetDiGraph<T> {
     /// # Parameters
     /// - `from`: Nodes to start from.
     pub fn reachable_nodes_from_multi(&self, from: EnumSet<T>) -> EnumSet<T> {
-        from.iter().map(|start_node| self.reachable_nodes(start_node))
-            .fold(EnumSet::empty(), |set0, set1| set0.union(set1))
+        // Expand the set until it stops growing.
+        let mut visited = from;
+        loop {
+            let expanded = visited.iter()
+                .fold(visited, |set, node| set.union(self.adjacent_nodes(node)));
+            if expanded == visited {
+                return visited;
+            }
+            visited = expanded;
+        }
     }
 
     /// Adds the specified directed edge to the graph if it is not present.
@@ -91,27 +178,45 @@ impl<T: EnumSetType + Enum<EnumSet<T>>> EnumSetDiGraph<T> {
     /// - `from`: Start node for the edge.
     /// - `to`: End node for the edge.
     pub fn insert_edge(&mut self, from: T, to: T) {
-        self.adj_sets[from].insert(to);
+        self.adj_bits[Self::index(from)] |= EnumSet::only(to).to_bits();
     }
 
     /// Constructs the transitive closure of this graph. The resulting graph has an edge from `x` to
     /// `y` for every pair of nodes `x` and `y` such that
     /// [`self.has_path(x, y)`](self::EnumSetDiGraph::has_path). Note that the resulting graph will
-    /// have a self loop on every node.
-    pub fn transitive_closure(&self) -> Self {
-        let mut output = EnumSetDiGraph::new();
-        for from in EnumSet::all() {
-            output.adj_sets[from] = self.reachable_nodes(from);
+    /// have a self loop on every node. This function can be evaluated at compile time.
+    pub const fn transitive_closure(&self) -> Self {
+        let mut adj_bits = self.adj_bits;
+        let mut node = 0;
+        while node < MAX_NODES {
+            adj_bits[node] |= 1 << node;
+            node += 1;
         }
 
-        output
+        // Warshall's algorithm, treating each adjacency set as a row of bits.
+        let mut via = 0;
+        while via < MAX_NODES {
+            let mut from = 0;
+            while from < MAX_NODES {
+                if adj_bits[from] & (1 << via) != 0 {
+                    adj_bits[from] |= adj_bits[via];
+                }
+                from += 1;
+            }
+            via += 1;
+        }
+
+        EnumSetDiGraph {
+            adj_bits,
+            node_type: PhantomData
+        }
     }
 
     /// Reverses the directions of all edges in the graph.
     pub fn reverse(&self) -> Self {
         let mut output = EnumSetDiGraph::new();
         for from in EnumSet::all() {
-            for to in self.adj_sets[from] {
+            for to in self.adjacent_nodes(from) {
                 output.insert_edge(to, from);
             }
         }
@@ -119,54 +224,297 @@ impl<T: EnumSetType + Enum<EnumSet<T>>> EnumSetDiGraph<T> {
         output
     }
 
-    /// Creates a new graph containing the union of all the edges in the two specified graphs.
-    pub fn union(&self, other: &EnumSetDiGraph<T>) -> Self {
+    /// Creates a new graph containing the union of all the edges in the two specified graphs. This
+    /// function can be evaluated at compile time.
+    pub const fn union(&self, other: &EnumSetDiGraph<T>) -> Self {
+        let mut adj_bits = self.adj_bits;
+        let mut node = 0;
+        while node < MAX_NODES {
+            adj_bits[node] |= other.adj_bits[node];
+            node += 1;
+        }
+
+        EnumSetDiGraph {
+            adj_bits,
+            node_type: PhantomData
+        }
+    }
+
+    /// Finds a shortest directed path from the specified start node to the specified end node, or
+    /// [`None`](std::option::Option::None) if there is no such path. The path is returned as a list
+    /// of nodes, including both end points, in which each node has an edge to the next. If `from`
+    /// and `to` are the same node, the zero-length path containing only that node is returned.
+    /// Among paths of equal length, the one found first when visiting nodes in enum declaration
+    /// order is returned.
+    ///
+    /// # Parameters
+    /// - `from`: Start node for the path.
+    /// - `to`: End node for the path.
+    pub fn path(&self, from: T, to: T) -> Option<Vec<T>> {
+        // Breadth-first search, recording the node from which each node was first reached.
+        let mut predecessors: Vec<(T, T)> = Vec::new();
+        let mut visited = EnumSet::only(from);
+        let mut frontier = vec![from];
+        while !visited.contains(to) && !frontier.is_empty() {
+            let mut next_frontier = Vec::new();
+            for node in frontier {
+                for next in self.adjacent_nodes(node) - visited {
+                    visited.insert(next);
+                    predecessors.push((next, node));
+                    next_frontier.push(next);
+                }
+            }
+            frontier = next_frontier;
+        }
+
+        if !visited.contains(to) {
+            return None;
+        }
+        let mut path = vec![to];
+        let mut current = to;
+        while let Some(&(_, predecessor)) = predecessors.iter().find(|(node, _)| *node == current) {
+            path.push(predecessor);
+            current = predecessor;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Constructs a transitive reduction of this graph, i.e. a graph with as few edges as possible
+    /// that has the same [`transitive_closure`](self::EnumSetDiGraph::transitive_closure). Self
+    /// loops are never included. Applied to a closure such as an implication graph, this recovers
+    /// the direct edges (e.g. Limiter to Dynamics, but not Limiter to itself).
+    ///
+    /// If the graph is acyclic, the reduction is unique and is a subgraph of this graph. Otherwise,
+    /// the nodes of each [strongly connected
+    /// component](self::EnumSetDiGraph::strongly_connected_components) are linked by a single
+    /// cycle in enum declaration order, and each remaining edge between two components is taken
+    /// from this graph.
+    pub fn transitive_reduction(&self) -> Self {
+        let closure = self.transitive_closure();
+        let components = self.strongly_connected_components();
         let mut output = EnumSetDiGraph::new();
-        for (node, adj_set) in output.adj_sets.iter_mut() {
-            *adj_set = self.adj_sets[node].union(other.adj_sets[node]);
+
+        for component in &components {
+            let nodes: Vec<T> = component.iter().collect();
+            if nodes.len() > 1 {
+                for (index, &node) in nodes.iter().enumerate() {
+                    output.insert_edge(node, nodes[(index + 1) % nodes.len()]);
+                }
+            }
         }
+
+        for from_component in &components {
+            for to_component in &components {
+                if from_component == to_component {
+                    continue;
+                }
+                let edge = from_component.iter()
+                    .find_map(|from| (self.adjacent_nodes(from) & *to_component).iter().next().map(|to| (from, to)));
+                if let Some((from, to)) = edge {
+                    // The edge is redundant if the target component can also be reached through a
+                    // third component.
+                    let redundant = components.iter()
+                        .filter(|&other| other != from_component && other != to_component)
+                        .any(|other| {
+                            let via = other.iter().next().expect("Components should be nonempty");
+                            closure.has_edge(from, via) && closure.has_edge(via, to)
+                        });
+                    if !redundant {
+                        output.insert_edge(from, to);
+                    }
+                }
+            }
+        }
+
         output
     }
-}
 
-impl<T: EnumSetType + Enum<EnumSet<T>>> Clone for EnumSetDiGraph<T> where
-    EnumMap<T, EnumSet<T>>: Clone
-{
-    fn clone(&self) -> Self {
-        EnumSetDiGraph {
-            adj_sets: self.adj_sets.clone()
+    /// Formats the graph in the Graphviz DOT language, labeling each node with its
+    /// [`Debug`](std::fmt::Debug) representation. See
+    /// [`to_dot_with_labels`](self::EnumSetDiGraph::to_dot_with_labels).
+    pub fn to_dot(&self) -> String
+        where T: Debug
+    {
+        self.to_dot_with_labels(|node| format!("{:?}", node))
+    }
+
+    /// Formats the graph in the Graphviz DOT language. Every node in the enum is included, in enum
+    /// declaration order. To draw a hierarchy without redundant edges, format the graph's
+    /// [`transitive_reduction`](self::EnumSetDiGraph::transitive_reduction) instead.
+    ///
+    /// # Parameters
+    /// - `node_label`: Function giving the label to display for each node.
+    pub fn to_dot_with_labels<F>(&self, node_label: F) -> String
+        where F: Fn(T) -> String
+    {
+        let nodes: Vec<T> = EnumSet::all().iter().collect();
+        let node_id = |node: T| nodes.iter().position(|&other| other == node)
+            .expect("Every node should be in the node list");
+        let mut output = String::from("digraph {\n");
+        for (index, &node) in nodes.iter().enumerate() {
+            output.push_str(&format!("    n{} [label={}];\n", index, dot_string(&node_label(node))));
         }
+        for &from in &nodes {
+            for to in self.adjacent_nodes(from) {
+                output.push_str(&format!("    n{} -> n{};\n", node_id(from), node_id(to)));
+            }
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Finds the strongly connected components of the graph, i.e. the maximal sets of nodes in
+    /// which every node has a path to every other node. Every node belongs to exactly one
+    /// component. The components are ordered by their first node in enum declaration order, which
+    /// makes the result stable.
+    pub fn strongly_connected_components(&self) -> Vec<EnumSet<T>> {
+        let reversed = self.reverse();
+        let mut remaining = EnumSet::all();
+        let mut components = Vec::new();
+        while let Some(node) = remaining.iter().next() {
+            let component = self.reachable_nodes(node) & reversed.reachable_nodes(node);
+            remaining -= component;
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Finds a directed cycle in the graph, if there is one. Self loops are ignored, because they
+    /// are present on every node of a transitive closure. The cycle is returned as a list of
+    /// distinct nodes in which each node has an edge to the next, and the last node has an edge to
+    /// the first. The search visits nodes in enum declaration order, so the same graph always
+    /// yields the same cycle.
+    pub fn find_cycle(&self) -> Option<Vec<T>> {
+        let mut finished = EnumSet::empty();
+        for start in EnumSet::all() {
+            if finished.contains(start) {
+                continue;
+            }
+
+            // Depth-first search, keeping the current path along with the unexplored neighbors of
+            // each node on it.
+            let mut on_path = EnumSet::only(start);
+            let mut path = vec![(start, self.adjacent_nodes(start) - start)];
+            while let Some((node, unexplored)) = path.last_mut() {
+                let node = *node;
+                match unexplored.iter().next() {
+                    Some(next) => {
+                        unexplored.remove(next);
+                        if on_path.contains(next) {
+                            let cycle_start = path.iter().position(|(path_node, _)| *path_node == next)
+                                .expect("Node on path should be in path list");
+                            return Some(path[cycle_start..].iter().map(|(path_node, _)| *path_node).collect());
+                        }
+                        if !finished.contains(next) {
+                            on_path.insert(next);
+                            path.push((next, self.adjacent_nodes(next) - next));
+                        }
+                    },
+                    None => {
+                        finished.insert(node);
+                        on_path.remove(node);
+                        path.pop();
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Checks if the graph has no directed cycles, ignoring self loops. See
+    /// [`find_cycle`](self::EnumSetDiGraph::find_cycle).
+    pub fn is_acyclic(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// Sorts the nodes of the graph so that every node comes before all nodes it has edges to.
+    /// Self loops are ignored. When several nodes could come next, the first in enum declaration
+    /// order is chosen, so the result is stable. For an implication graph, this lists each item
+    /// before the items it implies.
+    ///
+    /// # Errors
+    /// Returns an error containing a cycle (see
+    /// [`find_cycle`](self::EnumSetDiGraph::find_cycle)) if the graph is not acyclic.
+    pub fn topological_sort(&self) -> Result<Vec<T>, CycleError<T>> {
+        let reversed = self.reverse();
+        let mut remaining = EnumSet::all();
+        let mut order = Vec::with_capacity(remaining.len());
+        while !remaining.is_empty() {
+            let next = remaining.iter()
+                .find(|&node| (reversed.adjacent_nodes(node) & (remaining - node)).is_empty());
+            match next {
+                Some(node) => {
+                    order.push(node);
+                    remaining.remove(node);
+                },
+                None => {
+                    return Err(CycleError {
+                        cycle: self.find_cycle().expect("Graph without source node should have a cycle")
+                    });
+                }
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Gets the bit position of a node.
+    fn index(node: T) -> usize {
+        EnumSet::only(node).to_bits().trailing_zeros() as usize
+    }
+}
+
+impl<T: EnumSetType> Default for EnumSetDiGraph<T> {
+    fn default() -> Self {
+        EnumSetDiGraph::new()
     }
 }
 
-impl<T: EnumSetType + Enum<EnumSet<T>>> Copy for EnumSetDiGraph<T> where
-    EnumMap<T, EnumSet<T>>: Copy
-{}
+impl<T: EnumSetType> PartialEq for EnumSetDiGraph<T> {
+    fn eq(&self, other: &Self) -> bool {
+        EnumSet::<T>::all().iter().all(|node| self.adjacent_nodes(node) == other.adjacent_nodes(node))
+    }
+}
 
-impl<T: EnumSetType + Enum<EnumSet<T>>> PartialOrd for EnumSetDiGraph<T> {
+impl<T: EnumSetType> Eq for EnumSetDiGraph<T> {}
+
+impl<T: EnumSetType> Hash for EnumSetDiGraph<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for node in EnumSet::<T>::all() {
+            self.adjacent_nodes(node).to_bits().hash(state);
+        }
+    }
+}
+
+impl<T: EnumSetType> PartialOrd for EnumSetDiGraph<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl<T: EnumSetType + Enum<EnumSet<T>>> Ord for EnumSetDiGraph<T> {
+impl<T: EnumSetType> Ord for EnumSetDiGraph<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        // Use Enum::POSSIBLE_VALUES to guarantee a stable node ordering for the comparison.
-        // (EnumSet and EnumMap could probably also provide a stable ordering, but this is not
-        // guaranteed in their documentation.)
-        for node_id in 0..T::POSSIBLE_VALUES {
-            let node = T::from_usize(node_id);
-            let node_order = self.adj_sets[node].cmp(&other.adj_sets[node]);
-            if node_order != Ordering::Equal {
-                return node_order;
-            }
-        }
+        // EnumSet iterates in bit position order, which gives a stable node ordering for the
+        // comparison.
+        EnumSet::<T>::all().iter()
+            .map(|node| self.adjacent_nodes(node).to_bits().cmp(&other.adjacent_nodes(node).to_bits()))
+            .find(|node_order| *node_order != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
 
-        Ordering::Equal
+impl<T: EnumSetType + Debug> Debug for EnumSetDiGraph<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_map()
+            .entries(EnumSet::<T>::all().iter().map(|node| (node, self.adjacent_nodes(node))))
+            .finish()
     }
 }
 
-impl<T: EnumSetType + Enum<EnumSet<T>>> FromIterator<(T, T)> for EnumSetDiGraph<T> {
+impl<T: EnumSetType> FromIterator<(T, T)> for EnumSetDiGraph<T> {
     fn from_iter<I>(iter: I) -> Self where I: IntoIterator<Item = (T, T)> {
         let mut output = EnumSetDiGraph::new();
         for (from, to) in iter {
@@ -177,11 +525,108 @@ impl<T: EnumSetType + Enum<EnumSet<T>>> FromIterator<(T, T)> for EnumSetDiGraph<
     }
 }
 
-impl<T: EnumSetType + Enum<EnumSet<T>> + Send> FromParallelIterator<(T, T)> for EnumSetDiGraph<T> where
-    <T as Enum<EnumSet<T>>>::Array: Send
-{
+#[cfg(feature = "rayon")]
+impl<T: EnumSetType + Send> FromParallelIterator<(T, T)> for EnumSetDiGraph<T> {
     fn from_par_iter<I>(par_iter: I) -> Self where I: IntoParallelIterator<Item = (T, T)> {
         par_iter.into_par_iter().map(once).map(EnumSetDiGraph::from_iter)
             .reduce(EnumSetDiGraph::new, |graph0, graph1| graph0.union(&graph1))
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bundle_model::constants::PluginType;
+
+    #[test]
+    fn const_closure_matches_runtime_closure() {
+        const GRAPH: EnumSetDiGraph<PluginType> = enum_graph!(PluginType {
+            MultiEQ => EQ,
+            EQ => Filter,
+            Filter => MultiEQ,
+            Reverb => Delay
+        });
+        const CLOSURE: EnumSetDiGraph<PluginType> = GRAPH.transitive_closure();
+        let runtime_graph: EnumSetDiGraph<PluginType> = vec![
+            (PluginType::MultiEQ, PluginType::EQ),
+            (PluginType::EQ, PluginType::Filter),
+            (PluginType::Filter, PluginType::MultiEQ),
+            (PluginType::Reverb, PluginType::Delay)
+        ].into_iter().collect();
+        assert_eq!(GRAPH, runtime_graph);
+        assert_eq!(CLOSURE, runtime_graph.transitive_closure());
+        assert_eq!(CLOSURE.adjacent_nodes(PluginType::EQ), PluginType::MultiEQ | PluginType::EQ | PluginType::Filter);
+        assert!(GRAPH.has_path(PluginType::Filter, PluginType::EQ));
+        assert!(!GRAPH.has_edge(PluginType::Delay, PluginType::Reverb));
+        assert_eq!(GRAPH.union(&EnumSetDiGraph::new()), GRAPH);
+        assert_eq!(EnumSetDiGraph::<PluginType>::default(), EnumSetDiGraph::new());
+    }
+
+    #[test]
+    fn cycles_components_and_topological_order() {
+        let mut graph = enum_graph!(PluginType {
+            Compressor => Dynamics,
+            Limiter => Dynamics,
+            Waveshaper => Distortion
+        });
+        assert!(graph.is_acyclic());
+        assert!(graph.transitive_closure().is_acyclic());
+        let order = graph.transitive_closure().topological_sort().unwrap();
+        assert_eq!(order.len(), EnumSet::<PluginType>::all().len());
+        let position = |node| order.iter().position(|&other| other == node).unwrap();
+        assert!(position(PluginType::Limiter) < position(PluginType::Dynamics));
+        assert!(position(PluginType::Delay) < position(PluginType::Distortion));
+        assert_eq!(order[0], PluginType::Delay);
+
+        graph.insert_edge(PluginType::Dynamics, PluginType::Distortion);
+        graph.insert_edge(PluginType::Distortion, PluginType::Limiter);
+        let cycle = vec![PluginType::Distortion, PluginType::Limiter, PluginType::Dynamics];
+        assert_eq!(graph.find_cycle(), Some(cycle.clone()));
+        assert_eq!(graph.topological_sort(), Err(CycleError { cycle }));
+        let components = graph.strongly_connected_components();
+        assert_eq!(components.len(), EnumSet::<PluginType>::all().len() - 2);
+        assert!(components.contains(&(PluginType::Distortion | PluginType::Dynamics | PluginType::Limiter)));
+    }
+
+    #[test]
+    fn reduction_and_paths() {
+        let closure = enum_graph!(PluginType {
+            Limiter => Dynamics,
+            ParaEQ => EQ,
+            EQ => Filter
+        }).transitive_closure();
+        let reduction = closure.transitive_reduction();
+        assert!(reduction.has_edge(PluginType::ParaEQ, PluginType::EQ));
+        assert!(!reduction.has_edge(PluginType::ParaEQ, PluginType::Filter));
+        assert!(!reduction.has_edge(PluginType::Limiter, PluginType::Limiter));
+        assert_eq!(reduction.transitive_closure(), closure);
+        assert_eq!(reduction.path(PluginType::ParaEQ, PluginType::Filter),
+            Some(vec![PluginType::ParaEQ, PluginType::EQ, PluginType::Filter]));
+        assert_eq!(reduction.path(PluginType::Limiter, PluginType::Limiter), Some(vec![PluginType::Limiter]));
+        assert_eq!(reduction.path(PluginType::Dynamics, PluginType::Limiter), None);
+
+        let cyclic = enum_graph!(PluginType {
+            Delay => Reverb,
+            Reverb => Distortion,
+            Distortion => Delay,
+            Delay => Distortion,
+            Reverb => Filter,
+            Distortion => Filter
+        });
+        let reduction = cyclic.transitive_reduction();
+        assert_eq!(reduction.transitive_closure(), cyclic.transitive_closure());
+        let edge_count: usize = EnumSet::<PluginType>::all().iter()
+            .map(|node| reduction.adjacent_nodes(node).len())
+            .sum();
+        assert_eq!(edge_count, 4);
+    }
+
+    #[test]
+    fn dot_output_lists_nodes_and_edges() {
+        let graph = enum_graph!(PluginType { Reverb => Delay });
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph {\n    n0 [label=\"Delay\"];\n    n1 [label=\"Reverb\"];\n"));
+        assert!(dot.ends_with("    n1 -> n0;\n}\n"));
+        assert_eq!(dot_string("say \"hi\"\\"), "\"say \\\"hi\\\"\\\\\"");
+    }
+}
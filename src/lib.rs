@@ -10,6 +10,10 @@
 //! including features from the [standard extensions](http://lv2plug.in/ns/). If you need features
 //! beyond what this library provides, you may need to read or write the RDF bundle data directly
 //! (though feel free to request a change to this library).
+//!
+//! # Features
+//! - `rayon` (enabled by default): Adds parallel iterators, alongside the sequential ones, for
+//!   iterating over the sets and collections in the bundle model.
 
 #[macro_use]
 extern crate enumset;
@@ -18,6 +22,7 @@ extern crate enum_map;
 #[macro_use]
 extern crate lazy_static;
 
+#[macro_use]
 pub mod enum_graph;
 pub mod rdf_util;
 pub mod bundle_model;
@@ -3,6 +3,8 @@
 use std::str::FromStr;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, BTreeSet};
 
 use language_tags;
 
@@ -20,6 +22,13 @@ pub struct InvalidLangTagError {
     pub attempted_tag: String
 }
 
+/// Error type returned when trying to parse an invalid language range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidLangRangeError {
+    /// The invalid range string that we tried to parse.
+    pub attempted_range: String
+}
+
 /// Represents an IRI.
 ///
 /// Note: This type's implementations of [`Ord`](std::cmp::Ord) and
@@ -39,6 +48,17 @@ impl Iri {
     pub fn new(iri: String) -> Result<Self, InvalidIriError> {
         Ok(Iri { iri })
     }
+
+    /// Gets the IRI text.
+    pub fn as_str(&self) -> &str {
+        &self.iri
+    }
+}
+
+impl Borrow<str> for Iri {
+    fn borrow(&self) -> &str {
+        &self.iri
+    }
 }
 
 impl Display for Iri {
@@ -70,6 +90,116 @@ impl LangTag {
             Err(_) => Err(InvalidLangTagError { attempted_tag: tag })
         }
     }
+
+    /// Gets the language tag string.
+    pub fn as_str(&self) -> &str {
+        &self.tag
+    }
+
+    /// Gets the primary language subtag (e.g. `de` in `de-Latn-CH`), or
+    /// [`None`](std::option::Option::None) for private use and irregular grandfathered tags.
+    pub fn primary_language(&self) -> Option<&str> {
+        self.subtag_positions().0
+    }
+
+    /// Gets the script subtag (e.g. `Latn` in `de-Latn-CH`), or
+    /// [`None`](std::option::Option::None) if there is none.
+    pub fn script(&self) -> Option<&str> {
+        self.subtag_positions().1
+    }
+
+    /// Gets the region subtag (e.g. `CH` in `de-Latn-CH`, or `419` in `es-419`), or
+    /// [`None`](std::option::Option::None) if there is none.
+    pub fn region(&self) -> Option<&str> {
+        self.subtag_positions().2
+    }
+
+    /// Finds the primary language, script and region subtags, following the `langtag` production
+    /// of [RFC 5646](https://tools.ietf.org/html/rfc5646#section-2.1).
+    fn subtag_positions(&self) -> (Option<&str>, Option<&str>, Option<&str>) {
+        let mut subtags = self.tag.split('-').peekable();
+        let language = match subtags.next() {
+            Some(language) if language.len() >= 2 => language,
+            _ => return (None, None, None)
+        };
+
+        let is_alpha = |subtag: &str| subtag.bytes().all(|byte| byte.is_ascii_alphabetic());
+        let is_digit = |subtag: &str| subtag.bytes().all(|byte| byte.is_ascii_digit());
+        for _ in 0..3 {
+            if subtags.peek().is_some_and(|subtag| subtag.len() == 3 && is_alpha(subtag)) {
+                subtags.next();
+            }
+        }
+        let script = match subtags.peek() {
+            Some(subtag) if subtag.len() == 4 && is_alpha(subtag) => subtags.next(),
+            _ => None
+        };
+        let region = match subtags.peek() {
+            Some(subtag) if (subtag.len() == 2 && is_alpha(subtag)) || (subtag.len() == 3 && is_digit(subtag)) => {
+                subtags.next()
+            },
+            _ => None
+        };
+        (Some(language), script, region)
+    }
+
+    /// Gets the next more general language tag, by removing the last subtag (and any extension or
+    /// private use singleton left at the end). For example, the parent of `de-CH` is `de`. Returns
+    /// [`None`](std::option::Option::None) if the tag has only one subtag, or if removing the last
+    /// subtag would not leave a valid tag (e.g. for `x-foo` or `i-klingon`).
+    pub fn parent(&self) -> Option<LangTag> {
+        truncate_lang_range(&self.tag).and_then(|parent| LangTag::new(parent.to_string()).ok())
+    }
+
+    /// Gets the chain of progressively more general language tags to try when localized data is
+    /// not available for this tag, starting with this tag itself. For example, the chain for
+    /// `de-CH-1996` is `de-CH-1996`, `de-CH`, `de`.
+    pub fn fallback_chain(&self) -> Vec<LangTag> {
+        let mut chain = vec![self.clone()];
+        while let Some(parent) = chain.last().and_then(LangTag::parent) {
+            chain.push(parent);
+        }
+        chain
+    }
+
+    /// Checks if this tag matches a language range, using the filtering scheme of the range
+    /// (basic or extended) as defined in
+    /// [RFC 4647](https://tools.ietf.org/html/rfc4647#section-3.3). Comparisons are
+    /// case-insensitive.
+    pub fn matches(&self, range: &LangRange) -> bool {
+        let tag = self.tag.to_ascii_lowercase();
+        let range_text = range.range.to_ascii_lowercase();
+        if !range.extended {
+            return range_text == "*" || tag == range_text
+                || (tag.starts_with(&range_text) && tag[range_text.len()..].starts_with('-'));
+        }
+
+        let tag_subtags: Vec<&str> = tag.split('-').collect();
+        let range_subtags: Vec<&str> = range_text.split('-').collect();
+        if range_subtags[0] != "*" && range_subtags[0] != tag_subtags[0] {
+            return false;
+        }
+        let (mut range_index, mut tag_index) = (1, 1);
+        while range_index < range_subtags.len() {
+            if range_subtags[range_index] == "*" {
+                range_index += 1;
+            }
+            else if tag_index >= tag_subtags.len() {
+                return false;
+            }
+            else if range_subtags[range_index] == tag_subtags[tag_index] {
+                range_index += 1;
+                tag_index += 1;
+            }
+            else if tag_subtags[tag_index].len() == 1 {
+                return false;
+            }
+            else {
+                tag_index += 1;
+            }
+        }
+        true
+    }
 }
 
 impl Display for LangTag {
@@ -78,6 +208,73 @@ impl Display for LangTag {
     }
 }
 
+/// Represents a language range, as used to select language tags in
+/// [RFC 4647](https://tools.ietf.org/html/rfc4647#section-2). A range is either a basic range
+/// (e.g. `de-CH`, or `*` to match everything), which matches tags that start with the same
+/// subtags, or an extended range (e.g. `de-*-CH`), in which any subtag can be a wildcard.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LangRange {
+    /// The range string, made of 1-8 character alphanumeric subtags separated by hyphens.
+    range: String,
+
+    /// Indicates whether extended filtering should be used when matching tags.
+    extended: bool
+}
+
+impl LangRange {
+    /// Constructs a basic language range. Returns an error if the string is not a valid basic
+    /// language range, i.e. `*`, or a first subtag of 1-8 letters followed by any number of
+    /// subtags of 1-8 letters or digits.
+    pub fn basic(range: String) -> Result<Self, InvalidLangRangeError> {
+        let valid = range == "*" || range.split('-').enumerate().all(|(index, subtag)| {
+            is_range_subtag(subtag, index == 0)
+        });
+        if valid {
+            Ok(LangRange { range, extended: false })
+        }
+        else {
+            Err(InvalidLangRangeError { attempted_range: range })
+        }
+    }
+
+    /// Constructs an extended language range. Returns an error if the string is not a valid
+    /// extended language range, i.e. a basic language range in which any subtag may be `*`.
+    pub fn extended(range: String) -> Result<Self, InvalidLangRangeError> {
+        let valid = range.split('-').enumerate().all(|(index, subtag)| {
+            subtag == "*" || is_range_subtag(subtag, index == 0)
+        });
+        if valid {
+            Ok(LangRange { range, extended: true })
+        }
+        else {
+            Err(InvalidLangRangeError { attempted_range: range })
+        }
+    }
+
+    /// Gets the language range string.
+    pub fn as_str(&self) -> &str {
+        &self.range
+    }
+
+    /// Checks if this range uses extended filtering rather than basic filtering.
+    pub fn is_extended(&self) -> bool {
+        self.extended
+    }
+}
+
+impl Display for LangRange {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        f.write_str(&self.range)
+    }
+}
+
+/// Checks if a string is a valid non-wildcard subtag of a language range.
+fn is_range_subtag(subtag: &str, first: bool) -> bool {
+    (1..=8).contains(&subtag.len()) && subtag.bytes().all(|byte| {
+        if first { byte.is_ascii_alphabetic() } else { byte.is_ascii_alphanumeric() }
+    })
+}
+
 // TODO: Figure out if lang_tagged_literal_data_type and non_lang_tagged_literal_default_data_type
 // can be replaced with static constants. This would probably help with the efficiency of
 // Literal::with_data_type. The main difficulty is that their initialization requires heap
@@ -164,9 +361,303 @@ impl Literal {
 
     /// Gets the language tag, or [`None`](std::option::Option::None) if there is no language tag.
     pub fn lang_tag(&self) -> Option<&LangTag> {
-        match &self.lang_tag {
-            None => None,
-            Some(tag) => Some(&tag)
+        self.lang_tag.as_ref()
+    }
+
+    /// Interprets the literal value text as a number, converted to single precision. Boolean
+    /// values `true` and `false` are interpreted as 1 and 0. The data type is not checked, so
+    /// e.g. both `"5"^^xsd:integer` and `"5.0"^^xsd:float` are accepted. Returns
+    /// [`None`](std::option::Option::None) if the text is not numeric or boolean.
+    pub fn to_f32(&self) -> Option<f32> {
+        match self.value.trim() {
+            "true" => Some(1.0),
+            "false" => Some(0.0),
+            text => text.parse().ok()
+        }
+    }
+}
+
+/// Selects the literal best suited to a user's language preferences, using the "lookup" scheme
+/// from [RFC 4647](https://tools.ietf.org/html/rfc4647#section-3.4). Each preferred language is
+/// tried in order, and progressively shortened (e.g. `de-CH-1996` to `de-CH` to `de`) until a
+/// literal with a matching language tag is found. If no preferred language matches, a literal
+/// without a language tag is selected, and failing that, the first literal. Returns
+/// [`None`](std::option::Option::None) only if there are no literals.
+///
+/// # Parameters
+/// - `literals`: The candidate literals, e.g. the names of a plugin.
+/// - `preferred_langs`: Language tags in order of decreasing preference.
+pub fn lookup_literal<'a, I>(literals: I, preferred_langs: &[LangTag]) -> Option<&'a Literal>
+    where I: IntoIterator<Item = &'a Literal>
+{
+    let literals: Vec<&Literal> = literals.into_iter().collect();
+    for lang in preferred_langs {
+        let mut range = Some(lang.as_str());
+        while let Some(current) = range {
+            let found = literals.iter().find(|literal| {
+                literal.lang_tag().is_some_and(|tag| tag.as_str().eq_ignore_ascii_case(current))
+            });
+            if let Some(literal) = found {
+                return Some(literal);
+            }
+            range = truncate_lang_range(current);
         }
     }
-}
\ No newline at end of file
+
+    literals.iter().find(|literal| literal.lang_tag().is_none())
+        .or_else(|| literals.first())
+        .copied()
+}
+
+/// Removes the last subtag from a language range, as well as any single-character subtag (e.g. an
+/// extension singleton) that would then be left at the end. Returns
+/// [`None`](std::option::Option::None) if the range has only one subtag.
+fn truncate_lang_range(range: &str) -> Option<&str> {
+    let shortened = &range[..range.rfind('-')?];
+    match shortened.rfind('-') {
+        Some(start) if shortened.len() - start == 2 => Some(&shortened[..start]),
+        _ => Some(shortened)
+    }
+}
+
+/// Converts a POSIX locale name (e.g. `de_CH.UTF-8@euro`) to a language tag (e.g. `de-CH`). The
+/// codeset and modifier are ignored. Returns [`None`](std::option::Option::None) for the `C` and
+/// `POSIX` locales, and for locale names that don't start with a valid language tag.
+pub fn locale_to_lang_tag(locale: &str) -> Option<LangTag> {
+    let name = locale.split(['.', '@']).next().unwrap_or("");
+    match name {
+        "" | "C" | "POSIX" => None,
+        _ => LangTag::new(name.replace('_', "-")).ok()
+    }
+}
+
+/// Reads the user's preferred language for messages from the environment. As with POSIX message
+/// catalogs, the first nonempty variable out of `LC_ALL`, `LC_MESSAGES` and `LANG` is used. The
+/// result is meant to be passed to functions such as
+/// [`lookup_literal`](self::lookup_literal), and is empty if no language is configured.
+pub fn preferred_langs_from_env() -> Vec<LangTag> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|locale| locale_to_lang_tag(&locale))
+        .into_iter()
+        .collect()
+}
+
+/// Represents an RDF node that can appear in the subject or object position of a triple.
+///
+/// Note: This type's implementations of [`Ord`](std::cmp::Ord) and
+/// [`PartialOrd`](std::cmp::PartialOrd) have little semantic meaning, and exist mainly for use with
+/// collections that require an ordered element type.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Node {
+    /// Node identified by an IRI.
+    Iri(Iri),
+
+    /// Blank node, identified by a label that is only meaningful within a single graph.
+    BlankNode(String),
+
+    /// Literal node. Literals should only appear in the object position of a triple.
+    Literal(Literal)
+}
+
+impl Node {
+    /// Gets the IRI identifying the node, or [`None`](std::option::Option::None) if the node is not
+    /// an IRI node.
+    pub fn as_iri(&self) -> Option<&Iri> {
+        match self {
+            Node::Iri(iri) => Some(iri),
+            _ => None
+        }
+    }
+
+    /// Gets the literal value of the node, or [`None`](std::option::Option::None) if the node is
+    /// not a literal.
+    pub fn as_literal(&self) -> Option<&Literal> {
+        match self {
+            Node::Literal(literal) => Some(literal),
+            _ => None
+        }
+    }
+}
+
+impl From<Iri> for Node {
+    fn from(iri: Iri) -> Self {
+        Node::Iri(iri)
+    }
+}
+
+impl From<Literal> for Node {
+    fn from(literal: Literal) -> Self {
+        Node::Literal(literal)
+    }
+}
+
+/// Represents an in-memory RDF graph, i.e. a set of (subject, predicate, object) triples. This
+/// crate does not parse RDF syntax itself; the graph is expected to be filled using triples
+/// produced by an external parser (e.g. for the Turtle files in an LV2 bundle).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Graph {
+    /// Triples indexed by subject, then by predicate.
+    triples: BTreeMap<Node, BTreeMap<Iri, BTreeSet<Node>>>
+}
+
+impl Graph {
+    /// Constructs an empty graph.
+    pub fn new() -> Self {
+        Graph {
+            triples: BTreeMap::new()
+        }
+    }
+
+    /// Adds the specified triple to the graph if it is not present.
+    ///
+    /// # Parameters
+    /// - `subject`: Subject of the triple.
+    /// - `predicate`: Predicate of the triple.
+    /// - `object`: Object of the triple.
+    pub fn insert(&mut self, subject: Node, predicate: Iri, object: Node) {
+        self.triples.entry(subject).or_default().entry(predicate).or_default().insert(object);
+    }
+
+    /// Removes all triples with the specified subject and predicate.
+    pub fn remove_property(&mut self, subject: &Node, predicate: &str) {
+        if let Some(by_pred) = self.triples.get_mut(subject) {
+            by_pred.remove(predicate);
+            if by_pred.is_empty() {
+                self.triples.remove(subject);
+            }
+        }
+    }
+
+    /// Checks if the specified triple is present in the graph.
+    pub fn contains(&self, subject: &Node, predicate: &str, object: &Node) -> bool {
+        self.triples.get(subject)
+            .and_then(|by_pred| by_pred.get(predicate))
+            .is_some_and(|objects| objects.contains(object))
+    }
+
+    /// Gets an iterator over the objects of all triples with the specified subject and predicate.
+    /// The iterator will not repeat objects.
+    pub fn objects<'a>(&'a self, subject: &Node, predicate: &str) -> impl Iterator<Item = &'a Node> {
+        self.triples.get(subject)
+            .and_then(|by_pred| by_pred.get(predicate))
+            .into_iter()
+            .flat_map(|objects| objects.iter())
+    }
+
+    /// Gets the object of some triple with the specified subject and predicate, or
+    /// [`None`](std::option::Option::None) if there is no such triple. If there are multiple such
+    /// triples, which one is used is unspecified (but deterministic).
+    pub fn object(&self, subject: &Node, predicate: &str) -> Option<&Node> {
+        self.objects(subject, predicate).next()
+    }
+
+    /// Gets an iterator over the subjects of all triples with the specified predicate and object.
+    /// The iterator will not repeat subjects.
+    pub fn subjects<'a>(&'a self, predicate: &'a str, object: &Node) -> impl Iterator<Item = &'a Node> + 'a {
+        let object = object.clone();
+        self.triples.iter()
+            .filter(move |(_, by_pred)| {
+                by_pred.get(predicate).is_some_and(|objects| objects.contains(&object))
+            })
+            .map(|(subject, _)| subject)
+    }
+
+    /// Gets an iterator over the subjects of all triples with the specified predicate, regardless
+    /// of object. The iterator will not repeat subjects.
+    pub fn subjects_with_predicate<'a>(&'a self, predicate: &'a str) -> impl Iterator<Item = &'a Node> + 'a {
+        self.triples.iter()
+            .filter(move |(_, by_pred)| by_pred.contains_key(predicate))
+            .map(|(subject, _)| subject)
+    }
+
+    /// Gets an iterator over the predicates used in triples with the specified subject. The
+    /// iterator will not repeat predicates.
+    pub fn predicates<'a>(&'a self, subject: &Node) -> impl Iterator<Item = &'a Iri> {
+        self.triples.get(subject).into_iter().flat_map(|by_pred| by_pred.keys())
+    }
+
+    /// Adds all triples from another graph to this graph.
+    pub fn extend(&mut self, other: &Graph) {
+        for (subject, by_pred) in &other.triples {
+            for (predicate, objects) in by_pred {
+                for object in objects {
+                    self.insert(subject.clone(), predicate.clone(), object.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lang(tag: &str) -> LangTag {
+        LangTag::new(String::from(tag)).unwrap()
+    }
+
+    fn tagged(value: &str, tag: &str) -> Literal {
+        Literal::with_lang_tag(String::from(value), lang(tag))
+    }
+
+    #[test]
+    fn lookup_shortens_preferred_languages() {
+        let names: BTreeSet<Literal> = vec![
+            Literal::new(String::from("Delay")),
+            tagged("Verzögerung", "de"),
+            tagged("Retard", "fr-CA")
+        ].into_iter().collect();
+
+        assert_eq!(lookup_literal(&names, &[lang("de-CH-1996")]).unwrap().value(), "Verzögerung");
+        assert_eq!(lookup_literal(&names, &[lang("fr"), lang("de")]).unwrap().value(), "Verzögerung");
+        assert_eq!(lookup_literal(&names, &[lang("fr-CA-x-q")]).unwrap().value(), "Retard");
+        assert_eq!(lookup_literal(&names, &[lang("ja")]).unwrap().value(), "Delay");
+        assert!(lookup_literal(&BTreeSet::new(), &[lang("ja")]).is_none());
+    }
+
+    #[test]
+    fn ranges_are_matched_with_basic_and_extended_filtering() {
+        let basic = |range: &str| LangRange::basic(String::from(range)).unwrap();
+        let extended = |range: &str| LangRange::extended(String::from(range)).unwrap();
+
+        assert!(lang("de-CH").matches(&basic("de")));
+        assert!(lang("de-CH").matches(&basic("DE-ch")));
+        assert!(lang("de").matches(&basic("*")));
+        assert!(!lang("de-Latn-CH").matches(&basic("de-CH")));
+        assert!(!lang("dee").matches(&basic("de")));
+
+        assert!(lang("de-Latn-CH").matches(&extended("de-CH")));
+        assert!(lang("de-Latn-CH").matches(&extended("*-CH")));
+        assert!(lang("de-CH-1996").matches(&extended("de-*-1996")));
+        assert!(!lang("de-x-CH").matches(&extended("de-CH")));
+        assert!(!lang("fr-CH").matches(&extended("de-*")));
+
+        assert!(LangRange::basic(String::from("de-*")).is_err());
+        assert!(LangRange::extended(String::from("1de")).is_err());
+    }
+
+    #[test]
+    fn subtags_and_parents_are_found() {
+        let tag = lang("zh-Hant-HK");
+        assert_eq!((tag.primary_language(), tag.script(), tag.region()), (Some("zh"), Some("Hant"), Some("HK")));
+        let tag = lang("es-419");
+        assert_eq!((tag.primary_language(), tag.script(), tag.region()), (Some("es"), None, Some("419")));
+
+        assert_eq!(lang("de-CH-1996").fallback_chain(), vec![lang("de-CH-1996"), lang("de-CH"), lang("de")]);
+        assert_eq!(lang("en-a-bbb").parent(), Some(lang("en")));
+        assert_eq!(lang("de").parent(), None);
+        assert_eq!(lang("x-foo").parent(), None);
+        assert_eq!(lang("i-klingon").parent(), None);
+        assert_eq!(lang("x-foo-bar").fallback_chain(), vec![lang("x-foo-bar"), lang("x-foo")]);
+    }
+
+    #[test]
+    fn locale_names_are_converted() {
+        assert_eq!(locale_to_lang_tag("de_CH.UTF-8@euro"), Some(lang("de-CH")));
+        assert_eq!(locale_to_lang_tag("en"), Some(lang("en")));
+        assert_eq!(locale_to_lang_tag("C.UTF-8"), None);
+        assert_eq!(locale_to_lang_tag("POSIX"), None);
+    }
+}
@@ -0,0 +1,5 @@
+//! Runs the unit tests of the build script's Turtle parser, which cargo doesn't run for build
+//! scripts themselves.
+
+#[path = "../build/turtle.rs"]
+mod turtle;